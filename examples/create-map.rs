@@ -1,10 +1,8 @@
-#![expect(unused_imports)]
-
+use seeyou_cub::geojson::feature_collection;
 use seeyou_cub::{Airspace, CubReader, CubStyle};
 use serde_json::json;
 use std::env;
 use std::fs;
-use std::io;
 
 const HTML_TEMPLATE: &str = r#"<!DOCTYPE html>
 <html>
@@ -139,40 +137,15 @@ fn style_to_color(style: &CubStyle) -> &'static str {
     }
 }
 
-/// Converts an Airspace to a GeoJSON Feature
-#[cfg_attr(not(test), allow(dead_code))]
-fn airspace_to_geojson_feature(airspace: &Airspace) -> serde_json::Value {
-    // Convert points: radians -> degrees, lat/lon -> [lon, lat]
-    let mut coordinates: Vec<[f32; 2]> = airspace
-        .points
-        .iter()
-        .map(|pt| [pt.lon.to_degrees(), pt.lat.to_degrees()])
-        .collect();
-
-    // Ensure polygon is closed (first point = last point)
-    if let Some(first) = coordinates.first().copied()
-        && coordinates.last() != Some(&first)
-    {
-        coordinates.push(first);
-    }
-
-    json!({
-        "type": "Feature",
-        "geometry": {
-            "type": "Polygon",
-            "coordinates": [coordinates]
-        },
-        "properties": {
-            "name": airspace.name,
-            "class": format!("{:?}", airspace.class),
-            "style": format!("{:?}", airspace.style),
-            "min_alt": airspace.min_alt,
-            "max_alt": airspace.max_alt,
-            "min_alt_style": format!("{:?}", airspace.min_alt_style),
-            "max_alt_style": format!("{:?}", airspace.max_alt_style),
-            "color": style_to_color(&airspace.style)
+/// Converts airspaces to a GeoJSON FeatureCollection with a `color` property
+fn airspaces_to_geojson(airspaces: &[Airspace]) -> serde_json::Value {
+    let mut geojson = feature_collection(airspaces);
+    if let Some(features) = geojson["features"].as_array_mut() {
+        for (feature, airspace) in features.iter_mut().zip(airspaces) {
+            feature["properties"]["color"] = json!(style_to_color(&airspace.style));
         }
-    })
+    }
+    geojson
 }
 
 /// Generates HTML with embedded GeoJSON data and bounding box
@@ -210,7 +183,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     ];
 
     // Read all airspaces, collecting valid ones
-    let features: Vec<_> = reader
+    let airspaces: Vec<_> = reader
         .read_airspaces()
         .filter_map(|result| {
             result
@@ -230,14 +203,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 Some(airspace)
             }
         })
-        .map(|airspace| airspace_to_geojson_feature(&airspace))
         .collect();
 
     // Build GeoJSON FeatureCollection
-    let geojson = serde_json::json!({
-        "type": "FeatureCollection",
-        "features": features
-    });
+    let geojson = airspaces_to_geojson(&airspaces);
 
     let geojson_string = serde_json::to_string(&geojson)?;
 
@@ -270,7 +239,7 @@ mod tests {
     }
 
     #[test]
-    fn test_airspace_to_geojson_feature() {
+    fn test_airspaces_to_geojson() {
         let airspace = Airspace {
             name: "Test Zone".to_string(),
             points: vec![
@@ -288,8 +257,8 @@ mod tests {
             ..Default::default()
         };
 
-        let feature = airspace_to_geojson_feature(&airspace);
-        assert_debug_snapshot!(feature);
+        let geojson = airspaces_to_geojson(&[airspace]);
+        assert_debug_snapshot!(geojson);
     }
 
     #[test]
@@ -0,0 +1,319 @@
+//! Asynchronous CUB file reader for tokio (requires the `async` feature)
+
+use crate::error::{Error, Result};
+use crate::raw::{BorrowedStrings, HEADER_SIZE, Header, Item, read_item_data};
+use crate::reader::convert_to_airspace;
+use crate::slice::position;
+use crate::{Airspace, BoundingBox};
+use futures_util::Stream;
+use futures_util::stream;
+use std::borrow::Cow;
+use std::io::{ErrorKind, SeekFrom};
+use std::path::Path;
+use tokio::fs::File;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+
+/// Asynchronous CUB file reader with a `Stream`-based API
+///
+/// Works like `CubReader`, but reads from a tokio `AsyncRead + AsyncSeek`
+/// source. The header and items are read when the reader is created, item data
+/// is read lazily when the airspaces are streamed.
+///
+/// Since the item data has no explicit length, the reader determines the end
+/// of each block from the offset of the next block (or the end of the file),
+/// and reads it with a single read call.
+///
+/// # Example
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use seeyou_cub::AsyncCubReader;
+///
+/// # async fn example() -> Result<(), seeyou_cub::Error> {
+/// let mut reader = AsyncCubReader::from_path("airspace.cub").await?;
+///
+/// let mut airspaces = std::pin::pin!(reader.read_airspaces());
+/// while let Some(result) = airspaces.next().await {
+///     let airspace = result?;
+///     println!("{:?}: {} points", airspace.name, airspace.points.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct AsyncCubReader<R> {
+    reader: R,
+    header: Header,
+    items: Vec<Item>,
+    /// Sorted and deduplicated start offsets of all item data blocks and the
+    /// file length
+    data_bounds: Vec<u64>,
+    file_length: u64,
+}
+
+impl AsyncCubReader<File> {
+    /// Create a reader from a file path
+    ///
+    /// Opens the file and reads the header and items immediately.
+    pub async fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::open(path).await?;
+        Self::new(file).await
+    }
+}
+
+impl<R: AsyncRead + AsyncSeek + Unpin> AsyncCubReader<R> {
+    /// Create a reader from any `AsyncRead + AsyncSeek` source
+    ///
+    /// Reads the header and items immediately to validate the format and
+    /// store metadata. Errors carry the item index and byte offset of the
+    /// failing structure, like the errors of `CubReader`.
+    pub async fn new(mut reader: R) -> Result<Self> {
+        let mut buffer = vec![0; HEADER_SIZE];
+        let header = match reader.read_exact(&mut buffer).await {
+            Ok(_) => {
+                let mut remaining = &buffer[..];
+                Header::read(&mut remaining)
+                    .map_err(|error| error.with_context(None, position(&buffer, remaining)))
+            }
+            Err(error) => {
+                let position = reader.stream_position().await?;
+                Err(Error::from(error).with_context(None, position))
+            }
+        }?;
+
+        // Read the whole item table at once, then parse the items from memory
+        let size_of_item = header.size_of_item as usize;
+        let items_size = header.hdr_items.max(0) as usize * size_of_item;
+        let mut buffer = Vec::with_capacity(items_size);
+        (&mut reader)
+            .take(items_size as u64)
+            .read_to_end(&mut buffer)
+            .await?;
+
+        let mut items = Vec::with_capacity(header.hdr_items.max(0) as usize);
+        for index in 0..header.hdr_items.max(0) as usize {
+            let mut remaining = buffer.get(index * size_of_item..).unwrap_or_default();
+            let item = Item::read(&mut remaining, &header).map_err(|error| {
+                let offset = HEADER_SIZE as u64 + position(&buffer, remaining);
+                error.with_context(Some(index), offset)
+            })?;
+            items.push(item);
+        }
+
+        let file_length = reader.seek(SeekFrom::End(0)).await?;
+        let mut data_bounds: Vec<_> = items
+            .iter()
+            .map(|item| item_data_offset(&header, item))
+            .chain([file_length])
+            .collect();
+        data_bounds.sort_unstable();
+        data_bounds.dedup();
+
+        Ok(Self {
+            reader,
+            header,
+            items,
+            data_bounds,
+            file_length,
+        })
+    }
+
+    /// Get reference to a parsed header
+    pub fn raw_header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Get the CUB file title
+    pub fn title(&self) -> Cow<'_, str> {
+        self.header.title.decode()
+    }
+
+    /// Get bounding box covering all airspaces
+    ///
+    /// This value is read from the file header.
+    pub fn bounding_box(&self) -> &BoundingBox {
+        self.header.bounding_box()
+    }
+
+    /// Get references to all parsed items
+    pub fn raw_items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// Get the number of airspaces in the file
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check if the file contains no airspaces
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Read a single airspace by index
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    pub async fn airspace(&mut self, index: usize) -> Option<Result<Airspace>> {
+        if index >= self.items.len() {
+            return None;
+        }
+
+        Some(self.read_airspace(index).await)
+    }
+
+    /// Create stream over all airspaces in the file
+    ///
+    /// Returns a stream that yields `Result<Airspace>` for each airspace. The
+    /// stream is not `Unpin`, so it needs to be pinned (e.g. with
+    /// `std::pin::pin!`) before calling `StreamExt::next()`.
+    pub fn read_airspaces(&mut self) -> impl Stream<Item = Result<Airspace>> + '_ {
+        stream::unfold((self, 0), |(reader, index)| async move {
+            let result = reader.airspace(index).await?;
+            Some((result, (reader, index + 1)))
+        })
+    }
+
+    /// Read the item data block of an item and decode it into an `Airspace`
+    async fn read_airspace(&mut self, index: usize) -> Result<Airspace> {
+        let item = &self.items[index];
+        let start = item_data_offset(&self.header, item);
+
+        // Item data can't start beyond the end of the file
+        if start > self.file_length {
+            let error = Error::IoError(ErrorKind::UnexpectedEof.into());
+            return Err(error.with_context(Some(index), self.file_length));
+        }
+
+        // The block ends where the next block (or the file) starts
+        let next = self.data_bounds.partition_point(|&bound| bound <= start);
+        let end = self.data_bounds.get(next).copied().unwrap_or(start);
+
+        let mut buffer = vec![0; (end - start) as usize];
+        self.reader
+            .seek(SeekFrom::Start(start))
+            .await
+            .map_err(|error| Error::from(error).with_context(Some(index), start))?;
+        if let Err(error) = self.reader.read_exact(&mut buffer).await {
+            let position = self.reader.stream_position().await.unwrap_or(start);
+            return Err(Error::from(error).with_context(Some(index), position));
+        }
+
+        let mut reader = BorrowedStrings(&buffer);
+        read_item_data(&mut reader, &self.header)
+            .and_then(|(item_data, _)| convert_to_airspace(&self.header, item, item_data))
+            .map_err(|error| {
+                let offset = start + position(&buffer, reader.0);
+                error.with_context(Some(index), offset)
+            })
+    }
+}
+
+fn item_data_offset(header: &Header, item: &Item) -> u64 {
+    header.data_offset as u64 + item.points_offset as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CubReader;
+    use claims::{assert_matches, assert_none};
+    use futures_util::StreamExt;
+    use std::io::Cursor;
+
+    const FIXTURE: &str = "tests/fixtures/france_2024.07.02.cub";
+
+    #[tokio::test]
+    async fn matches_cub_reader() {
+        let mut reader = AsyncCubReader::from_path(FIXTURE)
+            .await
+            .expect("Failed to open fixture");
+
+        let mut expected_reader = CubReader::from_path(FIXTURE).expect("Failed to open fixture");
+        assert_eq!(reader.raw_header(), expected_reader.raw_header());
+        assert_eq!(reader.raw_items(), expected_reader.raw_items());
+        assert_eq!(reader.title(), expected_reader.title());
+
+        let expected: Vec<_> = expected_reader
+            .read_airspaces()
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+
+        let actual: Vec<_> = reader
+            .read_airspaces()
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+
+        // `Airspace` does not implement `PartialEq`
+        assert_eq!(format!("{actual:?}"), format!("{expected:?}"));
+    }
+
+    #[tokio::test]
+    async fn random_access_by_index() {
+        let bytes = std::fs::read(FIXTURE).expect("Failed to read fixture");
+        let mut reader = AsyncCubReader::new(Cursor::new(bytes))
+            .await
+            .expect("Failed to open fixture");
+
+        assert_eq!(reader.len(), 1368);
+
+        let airspace = reader
+            .airspace(0)
+            .await
+            .expect("Expected airspace at index")
+            .expect("Failed to read airspace");
+        assert_eq!(airspace.name, "R265 LA GREMUSE");
+
+        assert_none!(reader.airspace(1368).await);
+    }
+
+    #[tokio::test]
+    async fn item_data_beyond_end_of_file() {
+        let mut bytes = std::fs::read(FIXTURE).expect("Failed to read fixture");
+        let header = Header::read(&mut &bytes[..]).expect("Failed to read header");
+
+        // Point the first item beyond the end of the file
+        let mut item =
+            Item::read(&mut &bytes[HEADER_SIZE..], &header).expect("Failed to read item");
+        item.points_offset = bytes.len() as i32;
+        let mut buffer = Vec::new();
+        item.write(&mut buffer, &header)
+            .expect("Failed to write item");
+        bytes[HEADER_SIZE..HEADER_SIZE + buffer.len()].copy_from_slice(&buffer);
+
+        let length = bytes.len() as u64;
+        let mut reader = AsyncCubReader::new(Cursor::new(bytes))
+            .await
+            .expect("Failed to create reader");
+
+        let error = reader
+            .airspace(0)
+            .await
+            .expect("Expected airspace at index")
+            .expect_err("Expected an error");
+        assert_matches!(
+            error.inner(),
+            Error::IoError(error) if error.kind() == ErrorKind::UnexpectedEof
+        );
+        assert_eq!(error.item_index(), Some(0));
+        assert_eq!(error.offset(), Some(length));
+    }
+
+    #[tokio::test]
+    async fn truncated_items() {
+        let bytes = std::fs::read(FIXTURE).expect("Failed to read fixture");
+        let header = Header::read(&mut &bytes[..]).expect("Failed to read header");
+        let size_of_item = header.size_of_item as usize;
+
+        // Cut the file in the middle of the third item
+        let length = HEADER_SIZE + 2 * size_of_item + 10;
+        let error = AsyncCubReader::new(Cursor::new(bytes[..length].to_vec()))
+            .await
+            .err()
+            .expect("Expected an error");
+
+        assert_eq!(error.item_index(), Some(2));
+        assert_eq!(error.offset(), Some(length as u64));
+    }
+}
@@ -0,0 +1,322 @@
+//! Command-line tool for inspecting and converting CUB files
+//!
+//! Requires the `cli` feature:
+//!
+//! ```text
+//! cargo install seeyou-cub --features cli
+//! cub info airspace.cub
+//! cub dump airspace.cub
+//! cub convert airspace.cub airspace.txt
+//! ```
+
+use clap::{Arg, ArgMatches, Command, value_parser};
+use seeyou_cub::writer::CubWriter;
+use seeyou_cub::{Airspace, ByteOrder, CubReader, geojson, openair};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// File formats supported by `cub convert`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Cub,
+    OpenAir,
+    GeoJson,
+}
+
+impl Format {
+    const NAMES: [&str; 3] = ["cub", "openair", "geojson"];
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "cub" => Some(Format::Cub),
+            "openair" => Some(Format::OpenAir),
+            "geojson" => Some(Format::GeoJson),
+            _ => None,
+        }
+    }
+
+    /// Guess the format from the file extension
+    fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "cub" => Some(Format::Cub),
+            "txt" | "air" | "openair" => Some(Format::OpenAir),
+            "geojson" | "json" => Some(Format::GeoJson),
+            _ => None,
+        }
+    }
+}
+
+fn cli() -> Command {
+    let file = Arg::new("file")
+        .help("Path to the CUB file")
+        .required(true)
+        .value_parser(value_parser!(PathBuf));
+
+    let format = |name: &'static str, help: &'static str| {
+        Arg::new(name)
+            .long(name)
+            .help(help)
+            .value_name("FORMAT")
+            .value_parser(Format::NAMES)
+    };
+
+    Command::new("cub")
+        .about("Inspect and convert SeeYou CUB airspace files")
+        .version(env!("CARGO_PKG_VERSION"))
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .subcommand(
+            Command::new("info")
+                .about("Print the file header")
+                .arg(file.clone()),
+        )
+        .subcommand(
+            Command::new("dump")
+                .about("List all airspaces with style, class, altitudes and point count")
+                .arg(file),
+        )
+        .subcommand(
+            Command::new("convert")
+                .about("Convert between CUB, OpenAir and GeoJSON files")
+                .long_about(
+                    "Convert between CUB, OpenAir and GeoJSON files\n\n\
+                     Formats are detected from the file extension (.cub, .txt/.air/.openair, \
+                     .geojson/.json) unless given explicitly.",
+                )
+                .arg(
+                    Arg::new("input")
+                        .help("Input file")
+                        .required(true)
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    Arg::new("output")
+                        .help("Output file")
+                        .required(true)
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(format("from", "Input format"))
+                .arg(format("to", "Output format"))
+                .arg(Arg::new("title").long("title").help(
+                    "Title of the written CUB file (defaults to the input title or file name)",
+                )),
+        )
+}
+
+fn main() -> ExitCode {
+    let matches = cli().get_matches();
+
+    let result = match matches.subcommand() {
+        Some(("info", args)) => info(path_arg(args, "file"), &mut io::stdout().lock()),
+        Some(("dump", args)) => dump(path_arg(args, "file"), &mut io::stdout().lock()),
+        Some(("convert", args)) => convert(args),
+        _ => unreachable!("subcommand is required"),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn path_arg<'a>(args: &'a ArgMatches, name: &str) -> &'a Path {
+    args.get_one::<PathBuf>(name).expect("argument is required")
+}
+
+/// Print the header of a CUB file
+fn info<W: Write>(path: &Path, out: &mut W) -> Result<()> {
+    let reader = CubReader::from_path(path)?;
+    let header = reader.raw_header();
+    let bbox = reader.bounding_box();
+
+    let byte_order = match header.byte_order() {
+        ByteOrder::LE => "little-endian",
+        ByteOrder::BE => "big-endian",
+    };
+
+    writeln!(out, "Title:        {}", reader.title())?;
+    writeln!(out, "Airspaces:    {}", reader.len())?;
+    writeln!(
+        out,
+        "Bounding box: W={:.4} S={:.4} E={:.4} N={:.4}",
+        bbox.left.to_degrees(),
+        bbox.bottom.to_degrees(),
+        bbox.right.to_degrees(),
+        bbox.top.to_degrees()
+    )?;
+    writeln!(out, "Lo/La scale:  {:e}", header.lo_la_scale)?;
+    writeln!(out, "Byte order:   {byte_order}")?;
+
+    Ok(())
+}
+
+/// Print one line per airspace of a CUB file
+fn dump<W: Write>(path: &Path, out: &mut W) -> Result<()> {
+    let mut reader = CubReader::from_path(path)?;
+
+    for (index, airspace) in reader.read_airspaces().enumerate() {
+        let airspace = airspace?;
+        writeln!(
+            out,
+            "{index:>5}  {:<26} {:<8} {:>6} m {:<16} - {:>6} m {:<16} {:>5} points  {}",
+            format!("{:?}", airspace.style),
+            format!("{:?}", airspace.class),
+            airspace.min_alt,
+            format!("{:?}", airspace.min_alt_style),
+            airspace.max_alt,
+            format!("{:?}", airspace.max_alt_style),
+            airspace.points.len(),
+            airspace.name,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Convert a file between the supported formats
+fn convert(args: &ArgMatches) -> Result<()> {
+    let input = path_arg(args, "input");
+    let output = path_arg(args, "output");
+
+    let from = resolve_format(args, "from", input)?;
+    let to = resolve_format(args, "to", output)?;
+
+    let (title, airspaces) = read_airspaces(input, from)?;
+    let title = args.get_one::<String>("title").cloned().unwrap_or(title);
+
+    match to {
+        Format::Cub => CubWriter::new(title)
+            .add_airspaces(airspaces)
+            .write_to_path(output)?,
+        Format::OpenAir => openair::write_to_path(output, &airspaces)?,
+        Format::GeoJson => {
+            let mut writer = BufWriter::new(File::create(output)?);
+            writeln!(writer, "{}", geojson::feature_collection(&airspaces))?;
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+fn resolve_format(args: &ArgMatches, name: &str, path: &Path) -> Result<Format> {
+    match args.get_one::<String>(name) {
+        Some(format) => Ok(Format::from_name(format).expect("validated by clap")),
+        None => Format::from_path(path).ok_or_else(|| {
+            format!(
+                "Cannot detect format of {}, use --{name} to specify it",
+                path.display()
+            )
+            .into()
+        }),
+    }
+}
+
+/// Read all airspaces from a file, together with a title for the output
+fn read_airspaces(path: &Path, format: Format) -> Result<(String, Vec<Airspace>)> {
+    let file_stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    match format {
+        Format::Cub => {
+            let mut reader = CubReader::from_path(path)?;
+            let airspaces = reader
+                .read_airspaces()
+                .collect::<std::result::Result<_, _>>()?;
+            Ok((reader.title().into_owned(), airspaces))
+        }
+        Format::OpenAir => Ok((file_stem, openair::read_from_path(path)?)),
+        Format::GeoJson => {
+            let input = std::fs::read_to_string(path)?;
+            Ok((file_stem, geojson::parse(&input)?))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+
+    const FIXTURE: &str = "tests/fixtures/france_2024.07.02.cub";
+
+    #[test]
+    fn verify_cli() {
+        cli().debug_assert();
+    }
+
+    #[test]
+    fn format_detection() {
+        assert_eq!(Format::from_path(Path::new("a.CUB")), Some(Format::Cub));
+        assert_eq!(Format::from_path(Path::new("a.txt")), Some(Format::OpenAir));
+        assert_eq!(
+            Format::from_path(Path::new("a.json")),
+            Some(Format::GeoJson)
+        );
+        assert_eq!(Format::from_path(Path::new("a.kml")), None);
+        assert_eq!(Format::from_path(Path::new("a")), None);
+
+        for name in Format::NAMES {
+            assert!(Format::from_name(name).is_some());
+        }
+    }
+
+    #[test]
+    fn info_output() {
+        let mut out = Vec::new();
+        info(Path::new(FIXTURE), &mut out).expect("Failed to print info");
+        assert_snapshot!(String::from_utf8(out).unwrap());
+    }
+
+    #[test]
+    fn dump_output() {
+        let mut out = Vec::new();
+        dump(Path::new(FIXTURE), &mut out).expect("Failed to dump");
+
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.lines().count(), 1368);
+        assert_snapshot!(out.lines().take(5).collect::<Vec<_>>().join("\n"));
+    }
+
+    #[test]
+    fn convert_round_trip() {
+        let stem = format!("cub_cli_convert_round_trip_{}", std::process::id());
+        let dir = std::env::temp_dir();
+        let openair_path = dir.join(format!("{stem}.txt"));
+        let geojson_path = dir.join(format!("{stem}.geojson"));
+        let cub_path = dir.join(format!("{stem}.cub"));
+
+        let run = |args: &[&Path]| {
+            let mut argv = vec![Path::new("cub"), Path::new("convert")];
+            argv.extend(args);
+            let matches = cli().get_matches_from(argv);
+            let (_, args) = matches.subcommand().unwrap();
+            convert(args).expect("Failed to convert");
+        };
+
+        run(&[Path::new(FIXTURE), &openair_path]);
+        run(&[&openair_path, &geojson_path]);
+        run(&[&geojson_path, &cub_path]);
+
+        let reader = CubReader::from_path(&cub_path).expect("Failed to open result");
+        let (len, title) = (reader.len(), reader.title().into_owned());
+        drop(reader);
+
+        for path in [&openair_path, &geojson_path, &cub_path] {
+            std::fs::remove_file(path).expect("Failed to remove temporary file");
+        }
+
+        assert_eq!(len, 1368);
+        assert_eq!(title, stem);
+    }
+}
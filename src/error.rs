@@ -26,6 +26,81 @@ pub enum Error {
     #[error("SizeOfPoint is smaller than the minimum structure size")]
     UndersizedPoints { size_of_point: i32 },
 
+    #[error("CRC32 mismatch (expected 0x{expected:08X}, found 0x{found:08X})")]
+    CrcMismatch { expected: u32, found: u32 },
+
     #[error("Coordinate out of valid range (lat: {}, lon: {})", .point.lat, .point.lon)]
     CoordinateOutOfRange { point: Point },
+
+    #[error("Invalid OpenAir data on line {line}: {message}")]
+    InvalidOpenAir { line: usize, message: String },
+
+    #[error("Invalid vertical limit: {0}")]
+    InvalidVerticalLimit(String),
+
+    #[error("Invalid days active: {0}")]
+    InvalidDaysActive(String),
+
+    #[error("Invalid date/time: {0}")]
+    InvalidDateTime(String),
+
+    #[error("Invalid NOTAM: {0}")]
+    InvalidNotam(String),
+
+    /// An error with the location in the file where it occurred
+    ///
+    /// Attached automatically by `CubReader`. Use `Error::inner()` to get the
+    /// underlying error.
+    #[error("{error} (at offset {offset}{})", .item_index.map(|i| format!(", item {i}")).unwrap_or_default())]
+    WithContext {
+        /// Index of the item that failed to decode, if any
+        item_index: Option<usize>,
+        /// Absolute byte offset at which decoding failed, i.e. just after
+        /// the last byte that was read
+        offset: u64,
+        error: Box<Error>,
+    },
+
+    #[error("Invalid GeoJSON: {0}")]
+    InvalidGeoJson(String),
+}
+
+impl Error {
+    /// Attach the item index and absolute byte offset where the error occurred
+    ///
+    /// Errors that already carry context are returned unchanged.
+    pub fn with_context(self, item_index: Option<usize>, offset: u64) -> Self {
+        match self {
+            Self::WithContext { .. } => self,
+            error => Self::WithContext {
+                item_index,
+                offset,
+                error: Box::new(error),
+            },
+        }
+    }
+
+    /// Get the underlying error without location context
+    pub fn inner(&self) -> &Error {
+        match self {
+            Self::WithContext { error, .. } => error,
+            error => error,
+        }
+    }
+
+    /// Get the index of the item that failed to decode, if known
+    pub fn item_index(&self) -> Option<usize> {
+        match self {
+            Self::WithContext { item_index, .. } => *item_index,
+            _ => None,
+        }
+    }
+
+    /// Get the absolute byte offset at which decoding failed, if known
+    pub fn offset(&self) -> Option<u64> {
+        match self {
+            Self::WithContext { offset, .. } => Some(*offset),
+            _ => None,
+        }
+    }
 }
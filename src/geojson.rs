@@ -0,0 +1,378 @@
+//! GeoJSON import and export (requires the `geojson` feature)
+//!
+//! Airspaces are represented as GeoJSON `Feature`s with a `Polygon` geometry
+//! and the airspace attributes stored in the `properties` object:
+//!
+//! | Property              | Type   | Description                                      |
+//! | --------------------- | ------ | ------------------------------------------------ |
+//! | `name`                | string | Airspace name                                    |
+//! | `icao_code`           | string | ICAO code (optional)                             |
+//! | `style`               | string | `CubStyle` variant name, e.g. `"ControlZone"`    |
+//! | `class`               | string | `CubClass` variant name, e.g. `"ClassD"`         |
+//! | `extended_type`       | string | `ExtendedType` variant name (optional)           |
+//! | `min_alt`/`max_alt`   | number | Altitude limits in meters                        |
+//! | `min_alt_style`/`max_alt_style` | string | `AltStyle` variant name                |
+//! | `frequency`           | number | Primary frequency in MHz (optional)              |
+//! | `frequency_name`      | string | Primary frequency name (optional)                |
+//! | `secondary_frequency` | number | Secondary frequency in MHz (optional)            |
+//! | `exception_rules`     | string | Class exception rules (optional)                 |
+//! | `days_active`         | number | Raw `DaysActive` bits                            |
+//!
+//! Coordinates are written in degrees as `[lon, lat]` pairs, as required by
+//! the GeoJSON specification. The polygon ring is closed on export if the
+//! first and last points differ. On import all positions of the outer ring are
+//! kept.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use seeyou_cub::CubReader;
+//!
+//! let mut reader = CubReader::from_path("airspace.cub")?;
+//! let feature_collection = reader.to_geojson()?;
+//! println!("{feature_collection}");
+//! # Ok::<(), seeyou_cub::Error>(())
+//! ```
+
+use crate::error::{Error, Result};
+use crate::{Airspace, AltStyle, CubClass, CubReader, CubStyle, DaysActive, ExtendedType, Point};
+use serde_json::{Map, Value, json};
+use std::io::{Read, Seek};
+
+impl Airspace {
+    /// Convert the airspace to a GeoJSON `Feature`
+    ///
+    /// See the [module documentation](crate::geojson) for the property layout.
+    pub fn to_geojson_feature(&self) -> Value {
+        // Convert points: radians -> degrees, lat/lon -> [lon, lat]
+        let mut coordinates: Vec<[f32; 2]> = self
+            .points
+            .iter()
+            .map(|pt| [pt.lon.to_degrees(), pt.lat.to_degrees()])
+            .collect();
+
+        // Ensure polygon is closed (first point = last point)
+        if let Some(first) = coordinates.first().copied()
+            && coordinates.last() != Some(&first)
+        {
+            coordinates.push(first);
+        }
+
+        let mut properties = Map::new();
+        properties.insert("name".into(), json!(self.name));
+        if let Some(icao_code) = &self.icao_code {
+            properties.insert("icao_code".into(), json!(icao_code));
+        }
+        properties.insert("style".into(), json!(format!("{:?}", self.style)));
+        properties.insert("class".into(), json!(format!("{:?}", self.class)));
+        if let Some(extended_type) = self.extended_type {
+            properties.insert("extended_type".into(), json!(format!("{extended_type:?}")));
+        }
+        properties.insert("min_alt".into(), json!(self.min_alt));
+        properties.insert("max_alt".into(), json!(self.max_alt));
+        properties.insert(
+            "min_alt_style".into(),
+            json!(format!("{:?}", self.min_alt_style)),
+        );
+        properties.insert(
+            "max_alt_style".into(),
+            json!(format!("{:?}", self.max_alt_style)),
+        );
+        if let Some(frequency) = self.frequency {
+            properties.insert("frequency".into(), json!(frequency));
+        }
+        if let Some(frequency_name) = &self.frequency_name {
+            properties.insert("frequency_name".into(), json!(frequency_name));
+        }
+        if let Some(frequency) = self.secondary_frequency {
+            properties.insert("secondary_frequency".into(), json!(frequency));
+        }
+        if let Some(exception_rules) = &self.exception_rules {
+            properties.insert("exception_rules".into(), json!(exception_rules));
+        }
+        properties.insert("days_active".into(), json!(self.days_active.as_bits()));
+
+        json!({
+            "type": "Feature",
+            "geometry": {
+                "type": "Polygon",
+                "coordinates": [coordinates]
+            },
+            "properties": properties
+        })
+    }
+
+    /// Parse an airspace from a GeoJSON `Feature` with a `Polygon` geometry
+    ///
+    /// Only the outer ring of the polygon is used. Missing properties fall
+    /// back to the `Airspace::default()` values.
+    pub fn from_geojson_feature(feature: &Value) -> Result<Airspace> {
+        if feature["type"] != "Feature" {
+            return Err(invalid("expected a Feature"));
+        }
+
+        let geometry = &feature["geometry"];
+        if geometry["type"] != "Polygon" {
+            return Err(invalid("expected a Polygon geometry"));
+        }
+
+        let ring = geometry["coordinates"][0]
+            .as_array()
+            .ok_or_else(|| invalid("missing polygon coordinates"))?;
+
+        let points = ring
+            .iter()
+            .map(|position| {
+                let lon = position[0].as_f64();
+                let lat = position[1].as_f64();
+                match (lon, lat) {
+                    (Some(lon), Some(lat)) => Ok(Point::lat_lon(
+                        (lat as f32).to_radians(),
+                        (lon as f32).to_radians(),
+                    )),
+                    _ => Err(invalid("invalid position")),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let properties = &feature["properties"];
+        let string = |key: &str| properties[key].as_str().map(str::to_string);
+        let altitude = |key: &str| -> Result<i16> {
+            match &properties[key] {
+                Value::Null => Ok(0),
+                value => value
+                    .as_i64()
+                    .and_then(|v| i16::try_from(v).ok())
+                    .ok_or_else(|| invalid(&format!("invalid `{key}` property"))),
+            }
+        };
+
+        // `days_active` is stored in the 12 upper bits of `ActiveTime`
+        let days_active = match &properties["days_active"] {
+            Value::Null => 0,
+            value => value
+                .as_u64()
+                .and_then(|v| u16::try_from(v).ok())
+                .filter(|&v| v <= 0xFFF)
+                .ok_or_else(|| invalid("invalid `days_active` property"))?,
+        };
+
+        Ok(Airspace {
+            name: string("name").unwrap_or_default(),
+            icao_code: string("icao_code"),
+            points,
+            style: parse_name(properties, "style", &CubStyle::ALL)?.unwrap_or(CubStyle::Unknown),
+            class: parse_name(properties, "class", &CubClass::ALL)?.unwrap_or(CubClass::Unknown),
+            extended_type: parse_name(properties, "extended_type", &ExtendedType::ALL)?,
+            min_alt: altitude("min_alt")?,
+            max_alt: altitude("max_alt")?,
+            min_alt_style: parse_name(properties, "min_alt_style", &AltStyle::ALL)?
+                .unwrap_or(AltStyle::Unknown),
+            max_alt_style: parse_name(properties, "max_alt_style", &AltStyle::ALL)?
+                .unwrap_or(AltStyle::Unknown),
+            frequency: properties["frequency"].as_f64().map(|f| f as f32),
+            frequency_name: string("frequency_name"),
+            secondary_frequency: properties["secondary_frequency"].as_f64().map(|f| f as f32),
+            exception_rules: string("exception_rules"),
+            days_active: DaysActive::from_bits(days_active),
+            ..Default::default()
+        })
+    }
+}
+
+impl<R: Read + Seek> CubReader<R> {
+    /// Convert all airspaces in the file to a GeoJSON `FeatureCollection`
+    ///
+    /// Returns the first error encountered while decoding the airspaces.
+    pub fn to_geojson(&mut self) -> Result<Value> {
+        let airspaces = self.read_airspaces().collect::<Result<Vec<_>>>()?;
+        Ok(feature_collection(&airspaces))
+    }
+}
+
+/// Create a GeoJSON `FeatureCollection` from airspaces
+pub fn feature_collection<'a, I: IntoIterator<Item = &'a Airspace>>(airspaces: I) -> Value {
+    let features: Vec<_> = airspaces
+        .into_iter()
+        .map(Airspace::to_geojson_feature)
+        .collect();
+
+    json!({
+        "type": "FeatureCollection",
+        "features": features
+    })
+}
+
+/// Parse airspaces from a GeoJSON `FeatureCollection`
+pub fn parse_feature_collection(value: &Value) -> Result<Vec<Airspace>> {
+    if value["type"] != "FeatureCollection" {
+        return Err(invalid("expected a FeatureCollection"));
+    }
+
+    value["features"]
+        .as_array()
+        .ok_or_else(|| invalid("missing features"))?
+        .iter()
+        .map(Airspace::from_geojson_feature)
+        .collect()
+}
+
+/// Parse airspaces from a GeoJSON `FeatureCollection` string
+pub fn parse(input: &str) -> Result<Vec<Airspace>> {
+    let value: Value = serde_json::from_str(input).map_err(|e| invalid(&e.to_string()))?;
+    parse_feature_collection(&value)
+}
+
+/// Look up an enum variant by its `Debug` name in a string property
+fn parse_name<T: Copy + std::fmt::Debug>(
+    properties: &Value,
+    key: &str,
+    variants: &[T],
+) -> Result<Option<T>> {
+    let Some(name) = properties[key].as_str() else {
+        return Ok(None);
+    };
+
+    variants
+        .iter()
+        .find(|variant| format!("{variant:?}") == name)
+        .copied()
+        .map(Some)
+        .ok_or_else(|| invalid(&format!("unknown `{key}` value: {name}")))
+}
+
+fn invalid(message: &str) -> Error {
+    Error::InvalidGeoJson(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_lt, assert_matches, assert_ok};
+    use insta::assert_debug_snapshot;
+
+    fn airspace() -> Airspace {
+        Airspace {
+            name: "Test Zone".to_string(),
+            icao_code: Some("LFXX".to_string()),
+            points: vec![
+                Point::lat_lon(0.5, 0.4),
+                Point::lat_lon(0.6, 0.5),
+                Point::lat_lon(0.5, 0.6),
+            ],
+            style: CubStyle::ControlZone,
+            class: CubClass::ClassD,
+            extended_type: Some(ExtendedType::HelicopterTrafficZone),
+            min_alt: 0,
+            max_alt: 5000,
+            min_alt_style: AltStyle::AboveGroundLevel,
+            max_alt_style: AltStyle::FlightLevel,
+            frequency: Some(123.45),
+            frequency_name: Some("Tower".to_string()),
+            secondary_frequency: Some(118.5),
+            exception_rules: Some("Class E when tower closed".to_string()),
+            days_active: DaysActive::all(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn to_geojson_feature() {
+        assert_debug_snapshot!(airspace().to_geojson_feature());
+    }
+
+    #[test]
+    fn feature_round_trip() {
+        let original = airspace();
+        let read_back = Airspace::from_geojson_feature(&original.to_geojson_feature())
+            .expect("Failed to parse feature");
+
+        assert_eq!(read_back.name, original.name);
+        assert_eq!(read_back.icao_code, original.icao_code);
+        assert_eq!(read_back.style, original.style);
+        assert_eq!(read_back.class, original.class);
+        assert_eq!(read_back.extended_type, original.extended_type);
+        assert_eq!(read_back.min_alt, original.min_alt);
+        assert_eq!(read_back.max_alt, original.max_alt);
+        assert_eq!(read_back.min_alt_style, original.min_alt_style);
+        assert_eq!(read_back.max_alt_style, original.max_alt_style);
+        assert_eq!(read_back.frequency, original.frequency);
+        assert_eq!(read_back.frequency_name, original.frequency_name);
+        assert_eq!(read_back.secondary_frequency, original.secondary_frequency);
+        assert_eq!(read_back.exception_rules, original.exception_rules);
+        assert_eq!(read_back.days_active, original.days_active);
+
+        // Ring was closed on export
+        assert_eq!(read_back.points.len(), original.points.len() + 1);
+        for (a, b) in original.points.iter().zip(&read_back.points) {
+            assert_lt!((a.lat - b.lat).abs(), 1e-6);
+            assert_lt!((a.lon - b.lon).abs(), 1e-6);
+        }
+    }
+
+    #[test]
+    fn feature_collection_round_trip_france_fixture() {
+        let mut reader = CubReader::from_path("tests/fixtures/france_2024.07.02.cub")
+            .expect("Failed to open fixture");
+
+        let original = reader
+            .read_airspaces()
+            .collect::<Result<Vec<_>>>()
+            .expect("Failed to read airspaces");
+
+        let geojson = reader.to_geojson().expect("Failed to convert");
+        let read_back = parse(&geojson.to_string()).expect("Failed to parse");
+
+        assert_eq!(read_back.len(), original.len());
+        for (original, read_back) in original.iter().zip(&read_back) {
+            assert_eq!(read_back.name, original.name);
+            assert_eq!(read_back.style, original.style);
+            assert_eq!(read_back.class, original.class);
+            assert_eq!(read_back.min_alt, original.min_alt);
+            assert_eq!(read_back.max_alt, original.max_alt);
+            assert_eq!(read_back.frequency, original.frequency);
+            assert_eq!(read_back.days_active, original.days_active);
+        }
+    }
+
+    #[test]
+    fn parse_invalid_input() {
+        assert_matches!(parse("not json"), Err(Error::InvalidGeoJson(_)));
+        assert_matches!(
+            parse(r#"{"type": "Feature"}"#),
+            Err(Error::InvalidGeoJson(_))
+        );
+
+        let point_feature = json!({
+            "type": "Feature",
+            "geometry": { "type": "Point", "coordinates": [0, 0] },
+            "properties": {}
+        });
+        assert_matches!(
+            Airspace::from_geojson_feature(&point_feature),
+            Err(Error::InvalidGeoJson(_))
+        );
+
+        let mut feature = airspace().to_geojson_feature();
+        feature["properties"]["style"] = json!("Spaceport");
+        assert_matches!(
+            Airspace::from_geojson_feature(&feature),
+            Err(Error::InvalidGeoJson(_))
+        );
+
+        for days_active in [0x1000, 0x10000] {
+            let mut feature = airspace().to_geojson_feature();
+            feature["properties"]["days_active"] = json!(days_active);
+            assert_matches!(
+                Airspace::from_geojson_feature(&feature),
+                Err(Error::InvalidGeoJson(_))
+            );
+        }
+
+        // The reserved bit 11 fits into the field and is kept
+        let mut feature = airspace().to_geojson_feature();
+        feature["properties"]["days_active"] = json!(0xFFF);
+        let airspace = assert_ok!(Airspace::from_geojson_feature(&feature));
+        assert_eq!(airspace.days_active.as_bits(), 0xFFF);
+    }
+}
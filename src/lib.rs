@@ -1,13 +1,25 @@
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "async")]
+pub use crate::async_reader::AsyncCubReader;
 pub use crate::error::Error;
-pub use crate::reader::CubReader;
+pub use crate::reader::{CubReader, ReadOptions, ReadWarning, Strictness, WarningCause};
+pub use crate::slice::CubSlice;
 pub use crate::types::*;
 pub use crate::writer::CubWriter;
 
+#[cfg(feature = "async")]
+mod async_reader;
 mod error;
+#[cfg(feature = "geojson")]
+pub mod geojson;
+pub mod notam;
+pub mod openair;
 pub mod raw;
 mod reader;
+mod slice;
+pub mod terrain;
 mod types;
 pub mod utils;
+pub mod validate;
 pub mod writer;
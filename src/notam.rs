@@ -0,0 +1,591 @@
+//! ICAO NOTAM text parsing
+//!
+//! Converts raw ICAO NOTAMs into CUB NOTAM airspaces. The `Q)` line provides
+//! the NOTAM code, traffic, scope, vertical limits and a circle around the
+//! affected area. The `B)` and `C)` items provide the validity period, the
+//! `E)` item the plain language text.
+//!
+//! # Example
+//!
+//! ```
+//! use seeyou_cub::{CubStyle, notam};
+//!
+//! let text = "(A1234/24 NOTAMN
+//! Q) LFFF/QRTCA/IV/BO/W/000/065/4834N00220E005
+//! A) LFFF B) 2407020600 C) 2407021800
+//! E) TEMPORARY RESTRICTED AREA ACTIVATED)";
+//!
+//! let airspace = notam::parse(text)?.to_airspace()?;
+//! assert_eq!(airspace.style, CubStyle::Notam);
+//! assert_eq!(airspace.notam_id.as_deref(), Some("A1234/24"));
+//! # Ok::<(), seeyou_cub::Error>(())
+//! ```
+
+use crate::error::{Error, Result};
+use crate::utils::units::flight_level_to_meters;
+use crate::{
+    Airspace, AltStyle, CubStyle, DateTime, DaysActive, DaysActiveFlag, NotamCodes, NotamInfo,
+    NotamScope, NotamTraffic, NotamType, Point,
+};
+use std::str::FromStr;
+
+/// Meters per nautical mile
+const METERS_PER_NAUTICAL_MILE: f32 = 1852.;
+
+/// Number of points of the generated circle polygon
+const CIRCLE_POINTS: usize = 72;
+
+/// Items following the `Q)` line, in the order they appear in a NOTAM
+const ITEMS: [char; 8] = ['Q', 'A', 'B', 'C', 'D', 'E', 'F', 'G'];
+
+/// A parsed ICAO NOTAM
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notam {
+    /// NOTAM series, number and year (e.g. `A1234/24`)
+    pub id: Option<String>,
+    /// `NOTAMN`, `NOTAMR` or `NOTAMC`, or `NotamType::None` if the header is
+    /// missing
+    pub notam_type: NotamType,
+    pub q_line: QLine,
+    /// `A)` location indicators
+    pub locations: String,
+    /// `B)` start of validity
+    pub start: DateTime,
+    /// `C)` end of validity, `None` for permanent NOTAMs
+    pub end: Option<DateTime>,
+    /// `D)` day/time schedule
+    pub schedule: Option<String>,
+    /// `E)` NOTAM text
+    pub text: String,
+}
+
+impl Notam {
+    /// Get the NOTAM information stored in the ExtraData field
+    pub fn notam_info(&self) -> NotamInfo {
+        NotamInfo {
+            notam_type: self.notam_type,
+            codes: Some(self.q_line.codes),
+            traffic: self.q_line.traffic,
+            scope: self.q_line.scope,
+        }
+    }
+
+    /// Convert into a CUB NOTAM airspace
+    ///
+    /// The airspace is a circle around the `Q)` line center. Its limits are
+    /// the `Q)` line flight levels (as pressure altitude in meters) with
+    /// `AltStyle::Notam`. NOTAMs with a `D)` schedule are marked as active
+    /// "by NOTAM" instead of every day, since the schedule text is not
+    /// stored in CUB files.
+    ///
+    /// Returns `Error::InvalidNotam` if the `Q)` line codes can't be encoded
+    /// (see `NotamCodes::to_extra_data()`).
+    pub fn to_airspace(&self) -> Result<Airspace> {
+        let q_line = &self.q_line;
+
+        let days_active = match self.schedule {
+            Some(_) => DaysActive::empty().with(DaysActiveFlag::ByNotam),
+            None => DaysActive::all(),
+        };
+
+        Ok(Airspace {
+            style: CubStyle::Notam,
+            min_alt: flight_level_to_meters(q_line.lower).round() as i16,
+            max_alt: flight_level_to_meters(q_line.upper).round() as i16,
+            min_alt_style: AltStyle::Notam,
+            max_alt_style: AltStyle::Notam,
+            start_date: Some(self.start),
+            end_date: self.end,
+            days_active,
+            points: q_line.circle(),
+            name: self.id.clone().unwrap_or_else(|| q_line.code()),
+            notam_id: self.id.clone(),
+            notam_remarks: Some(self.text.clone()),
+            extra_data: self.notam_info().to_extra_data()?,
+            ..Default::default()
+        })
+    }
+}
+
+impl FromStr for Notam {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        parse(s)
+    }
+}
+
+/// The `Q)` line of a NOTAM
+///
+/// Format: `FIR/QCODE/TRAFFIC/PURPOSE/SCOPE/LOWER/UPPER/COORDINATES`, e.g.
+/// `LFFF/QRTCA/IV/BO/W/000/065/4834N00220E005`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QLine {
+    /// Flight information region (e.g. `LFFF`)
+    pub fir: String,
+    /// Subject and condition letters of the NOTAM code (e.g. `RT` and `CA`)
+    pub codes: NotamCodes,
+    pub traffic: NotamTraffic,
+    /// Purpose letters (e.g. `NBO`)
+    pub purpose: String,
+    pub scope: NotamScope,
+    /// Lower limit as flight level
+    pub lower: u16,
+    /// Upper limit as flight level (`999` for unlimited)
+    pub upper: u16,
+    pub center: Point,
+    /// Radius of the affected area in nautical miles (at least 1)
+    pub radius: u16,
+}
+
+impl QLine {
+    /// Get the five letter NOTAM code (e.g. `QRTCA`)
+    pub fn code(&self) -> String {
+        let NotamCodes { subject, action } = self.codes;
+        format!("Q{}{}{}{}", subject.0, subject.1, action.0, action.1)
+    }
+
+    /// Generate a polygon approximating the affected area
+    ///
+    /// The ring is closed, i.e. the first point is repeated at the end.
+    pub fn circle(&self) -> Vec<Point> {
+        let radius = self.radius as f32 * METERS_PER_NAUTICAL_MILE;
+        let mut points: Vec<_> = (0..CIRCLE_POINTS)
+            .map(|i| {
+                let bearing = std::f32::consts::TAU * i as f32 / CIRCLE_POINTS as f32;
+                self.center.destination(bearing, radius)
+            })
+            .collect();
+        points.push(points[0]);
+        points
+    }
+}
+
+impl FromStr for QLine {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        parse_q_line(s).map_err(Error::InvalidNotam)
+    }
+}
+
+/// Parse an ICAO NOTAM
+///
+/// The text may be wrapped in parentheses and span multiple lines. The
+/// header (e.g. `A1234/24 NOTAMN`) is optional. The `Q)`, `A)` and `B)`
+/// items are required, while `C)`, `D)` and `E)` are optional. `F)` and
+/// `G)` are ignored, since the `Q)` line already contains the vertical
+/// limits.
+///
+/// # Returns
+///
+/// The parsed NOTAM, or `Error::InvalidNotam` if a required item is missing
+/// or malformed
+pub fn parse(text: &str) -> Result<Notam> {
+    parse_notam(text).map_err(Error::InvalidNotam)
+}
+
+fn parse_notam(text: &str) -> std::result::Result<Notam, String> {
+    let text = text.trim();
+    let text = text.strip_prefix('(').unwrap_or(text);
+    let text = text.strip_suffix(')').unwrap_or(text);
+
+    let items = split_items(text);
+    let item = |letter| {
+        items
+            .iter()
+            .find(|(l, _)| *l == Some(letter))
+            .map(|(_, value)| *value)
+    };
+
+    let header = item_header(&items);
+    let (id, notam_type) = parse_header(header)?;
+
+    let q_line = parse_q_line(item('Q').ok_or("missing Q) line")?)?;
+    let locations = item('A').ok_or("missing A) item")?.to_string();
+    let start = parse_date_time(item('B').ok_or("missing B) item")?)?;
+
+    let end = match item('C') {
+        None => None,
+        Some(value) if value.eq_ignore_ascii_case("PERM") => None,
+        Some(value) => {
+            let value = value.strip_suffix("EST").unwrap_or(value).trim_end();
+            Some(parse_date_time(value)?)
+        }
+    };
+
+    let schedule = item('D').filter(|s| !s.is_empty()).map(str::to_string);
+    let text = item('E').unwrap_or_default().to_string();
+
+    Ok(Notam {
+        id,
+        notam_type,
+        q_line,
+        locations,
+        start,
+        end,
+        schedule,
+        text,
+    })
+}
+
+/// Split NOTAM text into the header and the `Q)` to `G)` items
+///
+/// Items are only recognized in their expected order, so that e.g. an `A)`
+/// within the `E)` text does not start a new item.
+fn split_items(text: &str) -> Vec<(Option<char>, &str)> {
+    let mut items = Vec::new();
+    let mut current = None;
+    let mut start = 0;
+    let mut next_index = 0;
+
+    let bytes = text.as_bytes();
+    let mut position = 0;
+    while position + 1 < bytes.len() {
+        let at_boundary = position == 0 || bytes[position - 1].is_ascii_whitespace();
+        let letter = bytes[position].to_ascii_uppercase() as char;
+        let item_index = ITEMS[next_index..].iter().position(|&l| l == letter);
+
+        match item_index {
+            Some(offset) if at_boundary && bytes[position + 1] == b')' => {
+                items.push((current, text[start..position].trim()));
+                current = Some(letter);
+                next_index += offset + 1;
+                position += 2;
+                start = position;
+
+                if next_index == ITEMS.len() {
+                    break;
+                }
+            }
+            _ => position += 1,
+        }
+    }
+    items.push((current, text[start..].trim()));
+
+    items
+}
+
+/// Get the text before the first item
+fn item_header<'a>(items: &[(Option<char>, &'a str)]) -> &'a str {
+    match items.first() {
+        Some((None, header)) => header,
+        _ => "",
+    }
+}
+
+/// Parse a header like `A1234/24 NOTAMN` or `A1235/24 NOTAMR A1234/24`
+fn parse_header(header: &str) -> std::result::Result<(Option<String>, NotamType), String> {
+    let tokens: Vec<_> = header.split_whitespace().collect();
+    let Some(index) = tokens
+        .iter()
+        .position(|token| token.to_ascii_uppercase().starts_with("NOTAM"))
+    else {
+        return Ok((None, NotamType::None));
+    };
+
+    let notam_type = match tokens[index].to_ascii_uppercase().as_str() {
+        "NOTAMN" => NotamType::New,
+        "NOTAMR" => NotamType::Replace,
+        "NOTAMC" => NotamType::Cancel,
+        other => return Err(format!("invalid NOTAM type: {other}")),
+    };
+
+    let id = index.checked_sub(1).map(|i| tokens[i].to_string());
+    Ok((id, notam_type))
+}
+
+fn parse_q_line(value: &str) -> std::result::Result<QLine, String> {
+    let invalid = |field: &str| format!("invalid Q) line {field}: {value}");
+
+    let fields: Vec<_> = value.split('/').map(str::trim).collect();
+    let [
+        fir,
+        code,
+        traffic,
+        purpose,
+        scope,
+        lower,
+        upper,
+        coordinates,
+    ] = fields[..]
+    else {
+        return Err(format!("invalid Q) line: {value}"));
+    };
+
+    let codes = parse_code(code).ok_or_else(|| invalid("code"))?;
+
+    let traffic = match traffic.to_ascii_uppercase().as_str() {
+        "" => NotamTraffic::Miscellaneous,
+        "I" => NotamTraffic::Ifr,
+        "V" => NotamTraffic::Vfr,
+        "IV" => NotamTraffic::IfrAndVfr,
+        "K" => NotamTraffic::Checklist,
+        _ => return Err(invalid("traffic")),
+    };
+
+    let scope = match scope.to_ascii_uppercase().as_str() {
+        "A" => NotamScope::Aerodrome,
+        "E" => NotamScope::EnRoute,
+        "W" => NotamScope::NavWarning,
+        "AE" => NotamScope::AerodromeAndEnRoute,
+        "AW" => NotamScope::AerodromeAndNavWarning,
+        "K" => NotamScope::Checklist,
+        _ => return Err(invalid("scope")),
+    };
+
+    let lower = parse_digits(lower, 3).ok_or_else(|| invalid("lower limit"))?;
+    let upper = parse_digits(upper, 3).ok_or_else(|| invalid("upper limit"))?;
+    if lower > upper {
+        return Err(invalid("limits"));
+    }
+
+    let (center, radius) = parse_coordinates(coordinates).ok_or_else(|| invalid("coordinates"))?;
+
+    Ok(QLine {
+        fir: fir.to_string(),
+        codes,
+        traffic,
+        purpose: purpose.to_string(),
+        scope,
+        lower,
+        upper,
+        center,
+        radius,
+    })
+}
+
+/// Parse a five letter NOTAM code like `QRTCA`
+fn parse_code(code: &str) -> Option<NotamCodes> {
+    let code = code.to_ascii_uppercase();
+    let letters: Vec<char> = code.strip_prefix('Q')?.chars().collect();
+    let [s1, s2, a1, a2] = letters[..] else {
+        return None;
+    };
+
+    NotamCodes::new((s1, s2), (a1, a2)).ok()
+}
+
+/// Parse a center and radius like `4834N00220E005`
+fn parse_coordinates(value: &str) -> Option<(Point, u16)> {
+    let value = value.to_ascii_uppercase();
+    if value.len() != 14 || !value.is_ascii() {
+        return None;
+    }
+
+    let lat = parse_digits(&value[0..2], 2)? as f32 + parse_minutes(&value[2..4])?;
+    let lon = parse_digits(&value[5..8], 3)? as f32 + parse_minutes(&value[8..10])?;
+    let lat = match &value[4..5] {
+        "N" => lat,
+        "S" => -lat,
+        _ => return None,
+    };
+    let lon = match &value[10..11] {
+        "E" => lon,
+        "W" => -lon,
+        _ => return None,
+    };
+    // A zero radius would result in a degenerate circle
+    let radius = parse_digits(&value[11..14], 3).filter(|&radius| radius > 0)?;
+
+    let center = Point::lat_lon(lat.to_radians(), lon.to_radians());
+    center.is_valid().then_some((center, radius))
+}
+
+/// Parse two digits of minutes into fractional degrees
+fn parse_minutes(value: &str) -> Option<f32> {
+    let minutes = parse_digits(value, 2)?;
+    (minutes < 60).then_some(minutes as f32 / 60.)
+}
+
+/// Parse exactly `len` decimal digits
+fn parse_digits(value: &str, len: usize) -> Option<u16> {
+    if value.len() != len || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+/// Parse a `YYMMDDhhmm` date and time (UTC)
+fn parse_date_time(value: &str) -> std::result::Result<DateTime, String> {
+    let invalid = || format!("invalid date/time: {value}");
+
+    let field = |range: std::ops::Range<usize>| {
+        value
+            .get(range)
+            .and_then(|digits| parse_digits(digits, 2))
+            .ok_or_else(invalid)
+    };
+
+    if value.len() != 10 {
+        return Err(invalid());
+    }
+
+    DateTime::new(
+        2000 + field(0..2)? as u32,
+        field(2..4)? as u8,
+        field(4..6)? as u8,
+        field(6..8)? as u8,
+        field(8..10)? as u8,
+    )
+    .map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CubReader;
+    use crate::validate::validate_airspace;
+    use crate::writer::CubWriter;
+    use claims::{assert_lt, assert_matches, assert_none, assert_ok, assert_some, assert_some_eq};
+    use std::io::Cursor;
+
+    const NOTAM: &str = "(A1235/24 NOTAMR A1234/24
+Q) LFFF/QRTCA/IV/BO/AW/000/065/4834N00220E005
+A) LFPG LFPO B) 2407020600 C) 2407151800 EST
+D) DAILY 0600-1800
+E) TEMPORARY RESTRICTED AREA ACTIVATED
+DUE TO AIR SHOW (SEE SUP 123/24 A) 3)
+F) SFC G) FL065)";
+
+    #[test]
+    fn parse_notam() {
+        let notam = assert_ok!(parse(NOTAM));
+
+        assert_eq!(notam.id.as_deref(), Some("A1235/24"));
+        assert_eq!(notam.notam_type, NotamType::Replace);
+        assert_eq!(notam.locations, "LFPG LFPO");
+        assert_eq!(notam.start, assert_ok!(DateTime::new(2024, 7, 2, 6, 0)));
+        assert_some_eq!(notam.end, assert_ok!(DateTime::new(2024, 7, 15, 18, 0)));
+        assert_eq!(notam.schedule.as_deref(), Some("DAILY 0600-1800"));
+        assert_eq!(
+            notam.text,
+            "TEMPORARY RESTRICTED AREA ACTIVATED\nDUE TO AIR SHOW (SEE SUP 123/24 A) 3)"
+        );
+
+        let q_line = &notam.q_line;
+        assert_eq!(q_line.fir, "LFFF");
+        assert_eq!(q_line.code(), "QRTCA");
+        assert_eq!(q_line.codes.subject, ('R', 'T'));
+        assert_eq!(q_line.codes.action, ('C', 'A'));
+        assert_eq!(q_line.traffic, NotamTraffic::IfrAndVfr);
+        assert_eq!(q_line.purpose, "BO");
+        assert_eq!(q_line.scope, NotamScope::AerodromeAndNavWarning);
+        assert_eq!(q_line.lower, 0);
+        assert_eq!(q_line.upper, 65);
+        assert_lt!((q_line.center.lat.to_degrees() - 48.5667).abs(), 1e-3);
+        assert_lt!((q_line.center.lon.to_degrees() - 2.3333).abs(), 1e-3);
+        assert_eq!(q_line.radius, 5);
+    }
+
+    #[test]
+    fn to_airspace() {
+        let notam = assert_ok!(parse(NOTAM));
+        let airspace = assert_ok!(notam.to_airspace());
+
+        assert_eq!(airspace.style, CubStyle::Notam);
+        assert_eq!(airspace.min_alt_style, AltStyle::Notam);
+        assert_eq!(airspace.max_alt_style, AltStyle::Notam);
+        assert_eq!(airspace.min_alt, 0);
+        assert_eq!(airspace.max_alt, 1981);
+        assert!(airspace.contains_3d(notam.q_line.center, 1500.));
+        assert!(!airspace.contains_3d(notam.q_line.center, 2500.));
+        assert_eq!(airspace.name, "A1235/24");
+        assert_eq!(airspace.notam_id.as_deref(), Some("A1235/24"));
+        assert_eq!(airspace.notam_remarks.as_deref(), Some(notam.text.as_str()));
+        assert_eq!(airspace.start_date, Some(notam.start));
+        assert_eq!(airspace.end_date, notam.end);
+        assert_eq!(
+            airspace.days_active,
+            DaysActive::empty().with(DaysActiveFlag::ByNotam)
+        );
+
+        assert_some_eq!(airspace.notam_info(), notam.notam_info());
+
+        assert_eq!(airspace.points.len(), CIRCLE_POINTS + 1);
+        assert_eq!(airspace.points.first(), airspace.points.last());
+        assert!(validate_airspace(&airspace).is_empty());
+        for point in &airspace.points {
+            let distance = notam.q_line.center.distance_to(*point);
+            assert_lt!((distance - 5. * METERS_PER_NAUTICAL_MILE).abs(), 1.);
+        }
+    }
+
+    #[test]
+    fn write_airspace() {
+        let airspace = assert_ok!(assert_ok!(parse(NOTAM)).to_airspace());
+
+        let mut cursor = Cursor::new(Vec::new());
+        assert_ok!(
+            CubWriter::new("NOTAM")
+                .add_airspace(airspace.clone())
+                .write(&mut cursor)
+        );
+
+        cursor.set_position(0);
+        let mut reader = assert_ok!(CubReader::new(&mut cursor));
+        let read_back = assert_ok!(assert_some!(reader.airspace(0)));
+        assert_eq!(read_back.style, CubStyle::Notam);
+        assert_eq!(read_back.notam_info(), airspace.notam_info());
+        assert_eq!(read_back.start_date, airspace.start_date);
+        assert_eq!(read_back.end_date, airspace.end_date);
+        assert_eq!(read_back.notam_id, airspace.notam_id);
+        assert_eq!(read_back.notam_remarks, airspace.notam_remarks);
+    }
+
+    #[test]
+    fn minimal_notam() {
+        let notam = assert_ok!(parse(
+            "Q) EDWW/QWELW/V/M/W/000/030/5300N00800W010 A) EDWW B) 2501011200 C) PERM"
+        ));
+
+        assert_none!(&notam.id);
+        assert_eq!(notam.notam_type, NotamType::None);
+        assert_eq!(notam.q_line.code(), "QWELW");
+        assert_lt!((notam.q_line.center.lon.to_degrees() + 8.).abs(), 1e-3);
+        assert_none!(notam.end);
+        assert_none!(&notam.schedule);
+        assert_eq!(notam.text, "");
+
+        let airspace = assert_ok!(notam.to_airspace());
+        assert_eq!(airspace.name, "QWELW");
+        assert_eq!(airspace.days_active, DaysActive::all());
+    }
+
+    #[test]
+    fn parse_q_line_errors() {
+        for value in [
+            "LFFF/QRTCA/IV/BO/W/000/065",
+            "LFFF/QRTC/IV/BO/W/000/065/4834N00220E005",
+            "LFFF/XRTCA/IV/BO/W/000/065/4834N00220E005",
+            "LFFF/QRTCA/X/BO/W/000/065/4834N00220E005",
+            "LFFF/QRTCA/IV/BO/EW/000/065/4834N00220E005",
+            "LFFF/QRTCA/IV/BO/W/070/065/4834N00220E005",
+            "LFFF/QRTCA/IV/BO/W/000/65/4834N00220E005",
+            "LFFF/QRTCA/IV/BO/W/000/065/4834X00220E005",
+            "LFFF/QRTCA/IV/BO/W/000/065/4860N00220E005",
+            "LFFF/QRTCA/IV/BO/W/000/065/4834N00220E",
+            "LFFF/QRTCA/IV/BO/W/000/065/4834N00220E000",
+        ] {
+            assert_matches!(
+                value.parse::<QLine>(),
+                Err(Error::InvalidNotam(_)),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        for text in [
+            "A) LFFF B) 2407020600",
+            "Q) LFFF/QRTCA/IV/BO/W/000/065/4834N00220E005 B) 2407020600",
+            "Q) LFFF/QRTCA/IV/BO/W/000/065/4834N00220E005 A) LFFF",
+            "Q) LFFF/QRTCA/IV/BO/W/000/065/4834N00220E005 A) LFFF B) 2402300600",
+            "Q) LFFF/QRTCA/IV/BO/W/000/065/4834N00220E005 A) LFFF B) 2407020600 C) TOMORROW",
+            "A1234/24 NOTAMX Q) LFFF/QRTCA/IV/BO/W/000/065/4834N00220E005 A) LFFF B) 2407020600",
+        ] {
+            assert_matches!(parse(text), Err(Error::InvalidNotam(_)), "{text}");
+        }
+    }
+}
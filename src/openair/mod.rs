@@ -0,0 +1,114 @@
+//! OpenAir airspace text format support
+//!
+//! [OpenAir](http://www.winpilot.com/UsersGuide/UserAirspace.asp) is a
+//! line-based text format that is widely used to distribute airspace data.
+//! This module converts OpenAir files into high-level `Airspace` values, which
+//! can then be written to a CUB file using `CubWriter`, and writes `Airspace`
+//! values (e.g. from `CubReader::read_airspaces()`) back to OpenAir text.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use seeyou_cub::openair;
+//! use seeyou_cub::writer::CubWriter;
+//!
+//! let airspaces = openair::read_from_path("airspace.txt")?;
+//!
+//! CubWriter::new("Converted from OpenAir")
+//!     .add_airspaces(airspaces)
+//!     .write_to_path("airspace.cub")?;
+//! # Ok::<(), seeyou_cub::Error>(())
+//! ```
+//!
+//! Converting a CUB file to OpenAir:
+//!
+//! ```no_run
+//! use seeyou_cub::{CubReader, openair};
+//!
+//! let airspaces: Vec<_> = CubReader::from_path("airspace.cub")?
+//!     .read_airspaces()
+//!     .collect::<Result<_, _>>()?;
+//!
+//! openair::write_to_path("airspace.txt", &airspaces)?;
+//! # Ok::<(), seeyou_cub::Error>(())
+//! ```
+
+mod reader;
+mod writer;
+
+pub use self::reader::{parse, read, read_from_path};
+pub use self::writer::{write, write_to_path};
+
+use crate::utils::units::METERS_PER_FOOT;
+use crate::{CubClass, CubStyle};
+
+/// Meters per nautical mile
+const METERS_PER_NAUTICAL_MILE: f32 = 1852.;
+
+/// Angular step between generated points on arcs and circles (in degrees)
+const ARC_STEP_DEGREES: f32 = 5.;
+
+/// Mapping between OpenAir `AC`/`AY` type codes and `CubStyle`
+///
+/// The first entry for each style is the preferred code when writing.
+const STYLE_CODES: &[(&str, CubStyle)] = &[
+    ("R", CubStyle::RestrictedArea),
+    ("Q", CubStyle::DangerArea),
+    ("P", CubStyle::ProhibitedArea),
+    ("GP", CubStyle::ProhibitedArea),
+    ("CTR", CubStyle::ControlZone),
+    ("TMZ", CubStyle::TransponderMandatoryZone),
+    ("RMZ", CubStyle::RadioMandatoryZone),
+    ("GSEC", CubStyle::GliderSector),
+    ("W", CubStyle::GliderSector),
+    ("TMA", CubStyle::TerminalControlArea),
+    ("CTA", CubStyle::ControlArea),
+    ("TRA", CubStyle::TemporaryReservedArea),
+    ("TSA", CubStyle::TemporarySegregatedArea),
+    ("TIZ", CubStyle::TrafficInformationZone),
+    ("TIA", CubStyle::TrafficInformationArea),
+    ("MATZ", CubStyle::MilitaryAerodromeTrafficZone),
+    ("ATZ", CubStyle::AerodromeTrafficZone),
+    ("AWY", CubStyle::Airway),
+    ("FIR", CubStyle::FlightInformationRegion),
+    ("DFIR", CubStyle::DelegatedFir),
+    ("FIS", CubStyle::FlightInformationServiceArea),
+    ("ADIZ", CubStyle::AirDefenceIdentificationZone),
+    ("ADX", CubStyle::Advisory),
+    ("SRZ", CubStyle::SpecialRulesZone),
+    ("TFR", CubStyle::TemporaryFlightRestriction),
+    ("LRMZ", CubStyle::LegacyRmz),
+    ("ASRA", CubStyle::AerialSportingAndRecreationArea),
+    ("TRZ", CubStyle::TransponderRecommendedZone),
+    ("VFR", CubStyle::VfrRoute),
+    ("ALERT", CubStyle::Alert),
+    ("WARNING", CubStyle::Warning),
+    ("NOTAM", CubStyle::Notam),
+];
+
+/// Mapping between OpenAir `AC` class letters and `CubClass`
+const CLASS_CODES: &[(&str, CubClass)] = &[
+    ("A", CubClass::ClassA),
+    ("B", CubClass::ClassB),
+    ("C", CubClass::ClassC),
+    ("D", CubClass::ClassD),
+    ("E", CubClass::ClassE),
+    ("F", CubClass::ClassF),
+    ("G", CubClass::ClassG),
+];
+
+/// Look up the `CubStyle` for an OpenAir type code
+fn style_from_code(code: &str) -> Option<CubStyle> {
+    STYLE_CODES
+        .iter()
+        .find(|(c, _)| c.eq_ignore_ascii_case(code))
+        .map(|&(_, style)| style)
+}
+
+/// Look up the `CubClass` for an OpenAir class letter
+fn class_from_code(code: &str) -> Option<CubClass> {
+    CLASS_CODES
+        .iter()
+        .find(|(c, _)| c.eq_ignore_ascii_case(code))
+        .map(|&(_, class)| class)
+}
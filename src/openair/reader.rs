@@ -0,0 +1,554 @@
+use super::{ARC_STEP_DEGREES, METERS_PER_NAUTICAL_MILE, class_from_code, style_from_code};
+use crate::error::{Error, Result};
+use crate::utils::ByteString;
+use crate::{Airspace, AltStyle, CubStyle, Point, VerticalLimit};
+use std::io::Read;
+use std::path::Path;
+
+/// Parse OpenAir text into airspaces
+///
+/// Supports the following records:
+///
+/// - `AC`: airspace class or type (starts a new airspace)
+/// - `AY`: airspace type (extended format)
+/// - `AN`: airspace name
+/// - `AL`/`AH`: lower and upper altitude limits
+/// - `AF`/`AG`: frequency and station name (extended format)
+/// - `DP`: polygon point
+/// - `V X=`/`V D=`: arc center and direction
+/// - `DC`: circle around the arc center
+/// - `DA`/`DB`: arcs around the arc center, by angles or by end points
+///
+/// Arcs and circles are converted into polygon points, and each ring is
+/// closed by repeating its first point at the end. Comments (`*`) and
+/// unsupported records (e.g. `AT`, `SP`, `SB`, `DY`) are ignored.
+///
+/// # Returns
+///
+/// The parsed airspaces, or `Error::InvalidOpenAir` for the first line that
+/// could not be parsed
+pub fn parse(input: &str) -> Result<Vec<Airspace>> {
+    let mut parser = Parser::default();
+
+    for (index, line) in input.lines().enumerate() {
+        parser
+            .parse_line(line.trim())
+            .map_err(|message| Error::InvalidOpenAir {
+                line: index + 1,
+                message,
+            })?;
+    }
+
+    Ok(parser.finish())
+}
+
+/// Read OpenAir text from a reader and parse it into airspaces
+///
+/// The input is decoded as UTF-8 with an Extended ASCII (CP1252) fallback,
+/// the same way strings in CUB files are decoded.
+pub fn read<R: Read>(mut reader: R) -> Result<Vec<Airspace>> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    parse(&ByteString::from(bytes).decode())
+}
+
+/// Read an OpenAir file from a path and parse it into airspaces
+///
+/// Convenience wrapper around `read()`.
+pub fn read_from_path<P: AsRef<Path>>(path: P) -> Result<Vec<Airspace>> {
+    let file = std::fs::File::open(path)?;
+    read(file)
+}
+
+/// Line-by-line OpenAir parser state
+#[derive(Default)]
+struct Parser {
+    airspaces: Vec<Airspace>,
+    current: Option<Airspace>,
+    /// Arc center set by `V X=`
+    center: Option<Point>,
+    /// Arc direction set by `V D=` (`V D=-` is counterclockwise)
+    counterclockwise: bool,
+}
+
+impl Parser {
+    fn parse_line(&mut self, line: &str) -> std::result::Result<(), String> {
+        if line.is_empty() || line.starts_with('*') {
+            return Ok(());
+        }
+
+        let (record, value) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let value = value.trim();
+
+        match record.to_ascii_uppercase().as_str() {
+            "AC" => {
+                self.start_airspace();
+                let airspace = self.current()?;
+                if let Some(class) = class_from_code(value) {
+                    airspace.class = class;
+                } else if let Some(style) = style_from_code(value) {
+                    airspace.style = style;
+                }
+            }
+            "AY" => {
+                let style = style_from_code(value).unwrap_or(CubStyle::Unknown);
+                self.current()?.style = style;
+            }
+            "AN" => self.current()?.name = value.to_string(),
+            "AL" => {
+                let (alt, style) = parse_altitude(value)?;
+                let airspace = self.current()?;
+                airspace.min_alt = alt;
+                airspace.min_alt_style = style;
+            }
+            "AH" => {
+                let (alt, style) = parse_altitude(value)?;
+                let airspace = self.current()?;
+                airspace.max_alt = alt;
+                airspace.max_alt_style = style;
+            }
+            "AF" => {
+                let frequency = value
+                    .parse::<f32>()
+                    .map_err(|_| format!("invalid frequency: {value}"))?;
+                self.current()?.frequency = Some(frequency);
+            }
+            "AG" => self.current()?.frequency_name = Some(value.to_string()),
+            "DP" => {
+                let point = parse_coordinate(value)?;
+                self.current()?.points.push(point);
+            }
+            "V" => self.parse_variable(value)?,
+            "DC" => {
+                let center = self.center()?;
+                let radius = parse_distance(value)?;
+                let points = (0..(360. / ARC_STEP_DEGREES) as usize).map(|i| {
+                    center.destination((i as f32 * ARC_STEP_DEGREES).to_radians(), radius)
+                });
+                self.current()?.points.extend(points);
+            }
+            "DA" => {
+                let center = self.center()?;
+                let mut parts = value.split(',').map(str::trim);
+                let (Some(radius), Some(start), Some(end), None) =
+                    (parts.next(), parts.next(), parts.next(), parts.next())
+                else {
+                    return Err(format!("invalid arc: {value}"));
+                };
+
+                let radius = parse_distance(radius)?;
+                let start = parse_angle(start)?;
+                let end = parse_angle(end)?;
+
+                let points = arc_points(center, radius, start, end, self.counterclockwise);
+                self.current()?.points.extend(points);
+            }
+            "DB" => {
+                let center = self.center()?;
+                let Some((from, to)) = value.split_once(',') else {
+                    return Err(format!("invalid arc: {value}"));
+                };
+
+                let from = parse_coordinate(from)?;
+                let to = parse_coordinate(to)?;
+                let radius = center.distance_to(from);
+                let start = center.bearing_to(from);
+                let end = center.bearing_to(to);
+
+                let mut points = arc_points(center, radius, start, end, self.counterclockwise);
+                // Use the exact end points instead of the calculated ones
+                if let Some(first) = points.first_mut() {
+                    *first = from;
+                }
+                if let Some(last) = points.last_mut() {
+                    *last = to;
+                }
+                self.current()?.points.extend(points);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn parse_variable(&mut self, value: &str) -> std::result::Result<(), String> {
+        let Some((name, value)) = value.split_once('=') else {
+            return Err(format!("invalid variable: {value}"));
+        };
+
+        match name.trim().to_ascii_uppercase().as_str() {
+            "X" => self.center = Some(parse_coordinate(value)?),
+            "D" => match value.trim() {
+                "+" => self.counterclockwise = false,
+                "-" => self.counterclockwise = true,
+                other => return Err(format!("invalid arc direction: {other}")),
+            },
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Finish the current airspace (if any) and start a new one
+    fn start_airspace(&mut self) {
+        self.finish_airspace();
+
+        self.current = Some(Airspace::default());
+        self.center = None;
+        self.counterclockwise = false;
+    }
+
+    fn current(&mut self) -> std::result::Result<&mut Airspace, String> {
+        self.current
+            .as_mut()
+            .ok_or_else(|| "record outside of airspace definition (missing AC)".to_string())
+    }
+
+    fn center(&self) -> std::result::Result<Point, String> {
+        self.center
+            .ok_or_else(|| "arc or circle without center (missing V X=)".to_string())
+    }
+
+    fn finish(mut self) -> Vec<Airspace> {
+        self.finish_airspace();
+        self.airspaces
+    }
+
+    /// Close the ring of the current airspace (if any) and store it
+    ///
+    /// OpenAir polygons are implicitly closed, while `Airspace::points` repeats
+    /// the first point at the end (see `validate::Rule::UnclosedRing`).
+    fn finish_airspace(&mut self) {
+        if let Some(mut airspace) = self.current.take() {
+            let points = &mut airspace.points;
+            if points.len() > 1 && points.first() != points.last() {
+                points.push(points[0]);
+            }
+            self.airspaces.push(airspace);
+        }
+    }
+}
+
+/// Generate points on an arc from `start` to `end` bearing (in radians)
+///
+/// Both end points are included. Identical start and end bearings produce a
+/// full circle.
+fn arc_points(
+    center: Point,
+    radius: f32,
+    start: f32,
+    end: f32,
+    counterclockwise: bool,
+) -> Vec<Point> {
+    let tau = std::f32::consts::TAU;
+    let (sweep, direction) = if counterclockwise {
+        ((start - end).rem_euclid(tau), -1.)
+    } else {
+        ((end - start).rem_euclid(tau), 1.)
+    };
+    let sweep = if sweep == 0. { tau } else { sweep };
+
+    // Allow for a small rounding error, so that e.g. a 90° arc results in exactly 18 steps
+    let steps = (sweep.to_degrees() / ARC_STEP_DEGREES - 1e-3)
+        .ceil()
+        .max(1.) as usize;
+    (0..=steps)
+        .map(|i| {
+            let bearing = start + direction * sweep * i as f32 / steps as f32;
+            center.destination(bearing, radius)
+        })
+        .collect()
+}
+
+/// Parse an OpenAir altitude string like `FL65`, `2500ft AMSL` or `SFC`
+///
+/// Uses the `VerticalLimit` grammar, except that the reference is optional
+/// and defaults to mean sea level, and that `0` is parsed as the surface.
+fn parse_altitude(value: &str) -> std::result::Result<(i16, AltStyle), String> {
+    let limit = match value.trim() {
+        "0" => Ok(VerticalLimit::Surface),
+        value => value
+            .parse::<VerticalLimit>()
+            .or_else(|_| format!("{value} MSL").parse()),
+    };
+
+    limit
+        .map(|limit| limit.to_raw())
+        .map_err(|_| format!("invalid altitude: {value}"))
+}
+
+/// Parse a distance in nautical miles and convert it to meters
+fn parse_distance(value: &str) -> std::result::Result<f32, String> {
+    value
+        .trim()
+        .parse::<f32>()
+        .map(|nm| nm * METERS_PER_NAUTICAL_MILE)
+        .map_err(|_| format!("invalid distance: {value}"))
+}
+
+/// Parse an angle in degrees and convert it to radians
+fn parse_angle(value: &str) -> std::result::Result<f32, String> {
+    value
+        .trim()
+        .parse::<f32>()
+        .map(f32::to_radians)
+        .map_err(|_| format!("invalid angle: {value}"))
+}
+
+/// Parse an OpenAir coordinate like `45:12:30 N 005:30:00 E`
+///
+/// Accepts `D:M:S`, `D:M.m` and decimal degree notation for both latitude
+/// and longitude, with optional whitespace before the hemisphere letter.
+fn parse_coordinate(value: &str) -> std::result::Result<Point, String> {
+    let invalid = || format!("invalid coordinate: {value}");
+
+    let upper = value.trim().to_ascii_uppercase();
+    let lat_end = upper.find(['N', 'S']).ok_or_else(invalid)?;
+    let (lat, rest) = upper.split_at(lat_end);
+    let (lat_hemisphere, rest) = rest.split_at(1);
+    let lon_end = rest.find(['E', 'W']).ok_or_else(invalid)?;
+    let (lon, lon_hemisphere) = rest.split_at(lon_end);
+
+    let mut lat = parse_degrees(lat).ok_or_else(invalid)?;
+    let mut lon = parse_degrees(lon).ok_or_else(invalid)?;
+    if lat_hemisphere == "S" {
+        lat = -lat;
+    }
+    if lon_hemisphere.starts_with('W') {
+        lon = -lon;
+    }
+
+    let point = Point::lat_lon(lat.to_radians(), lon.to_radians());
+    if !point.is_valid() {
+        return Err(invalid());
+    }
+
+    Ok(point)
+}
+
+/// Parse `D:M:S`, `D:M.m` or `D.d` into decimal degrees
+fn parse_degrees(value: &str) -> Option<f32> {
+    let mut parts = value.trim().split(':');
+    let degrees: f64 = parts.next()?.trim().parse().ok()?;
+    let minutes: f64 = parts.next().map_or(Some(0.), |m| m.trim().parse().ok())?;
+    let seconds: f64 = parts.next().map_or(Some(0.), |s| s.trim().parse().ok())?;
+    if parts.next().is_some() || minutes >= 60. || seconds >= 60. {
+        return None;
+    }
+
+    Some((degrees + minutes / 60. + seconds / 3600.) as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CubClass;
+    use crate::validate::{Rule, validate_airspace};
+    use claims::{assert_err, assert_lt, assert_matches};
+
+    const SAMPLE: &str = "\
+* Sample OpenAir file
+AC R
+AN R265 LA GREMUSE
+AL SFC
+AH FL65
+DP 45:12:30 N 005:30:00 E
+DP 45:15:00 N 005:35:00 E
+DP 45:10:00 N 005:40:00 E
+
+AC D
+AY CTR
+AN GRENOBLE CTR
+AF 120.400
+AG Grenoble Tower
+AL 2500ft AMSL
+AH 1500 m
+V X=45:21:45 N 005:19:45 E
+DC 5
+
+AC Q
+AN ARC TEST
+AL 1000ft AGL
+AH UNL
+V X=45:00:00 N 006:00:00 E
+V D=-
+DA 10, 90, 0
+DB 45:00:00 N 006:14:07 E, 45:10:00 N 006:00:00 E
+";
+
+    #[test]
+    fn parse_sample() {
+        let airspaces = parse(SAMPLE).expect("Failed to parse");
+        assert_eq!(airspaces.len(), 3);
+
+        let restricted = &airspaces[0];
+        assert_eq!(restricted.name, "R265 LA GREMUSE");
+        assert_eq!(restricted.style, CubStyle::RestrictedArea);
+        assert_eq!(restricted.class, CubClass::Unknown);
+        assert_eq!(restricted.min_alt, 0);
+        assert_eq!(restricted.min_alt_style, AltStyle::AboveGroundLevel);
+        assert_eq!(restricted.max_alt, 1981);
+        assert_eq!(restricted.max_alt_style, AltStyle::FlightLevel);
+        assert_eq!(restricted.points.len(), 4);
+        assert_eq!(restricted.points.first(), restricted.points.last());
+        assert_lt!(
+            (restricted.points[0].lat.to_degrees() - 45.208_33).abs(),
+            1e-4
+        );
+        assert_lt!((restricted.points[0].lon.to_degrees() - 5.5).abs(), 1e-4);
+
+        let ctr = &airspaces[1];
+        assert_eq!(ctr.name, "GRENOBLE CTR");
+        assert_eq!(ctr.style, CubStyle::ControlZone);
+        assert_eq!(ctr.class, CubClass::ClassD);
+        assert_eq!(ctr.frequency, Some(120.4));
+        assert_eq!(ctr.frequency_name.as_deref(), Some("Grenoble Tower"));
+        assert_eq!(ctr.min_alt, 762);
+        assert_eq!(ctr.min_alt_style, AltStyle::MeanSeaLevel);
+        assert_eq!(ctr.max_alt, 1500);
+        assert_eq!(ctr.max_alt_style, AltStyle::MeanSeaLevel);
+        // 72 points in 5° steps plus the closing point
+        assert_eq!(ctr.points.len(), 73);
+
+        let center = Point::lat_lon(45.3625_f32.to_radians(), 5.329_167_f32.to_radians());
+        for point in &ctr.points {
+            assert_lt!((center.distance_to(*point) - 5. * 1852.).abs(), 1.);
+        }
+
+        let arcs = &airspaces[2];
+        assert_eq!(arcs.style, CubStyle::DangerArea);
+        assert_eq!(arcs.min_alt, 305);
+        assert_eq!(arcs.min_alt_style, AltStyle::AboveGroundLevel);
+        assert_eq!(arcs.max_alt_style, AltStyle::Unlimited);
+        // 90° counterclockwise arc in 5° steps + 90° counterclockwise arc in 5° steps
+        // plus the closing point
+        assert_eq!(arcs.points.len(), 19 + 19 + 1);
+    }
+
+    #[test]
+    fn parsed_airspaces_are_closed() {
+        let airspaces = parse(SAMPLE).expect("Failed to parse");
+
+        // The polygon and the circle are valid as is
+        for airspace in &airspaces[..2] {
+            let issues = validate_airspace(airspace);
+            assert!(issues.is_empty(), "{}: {issues:?}", airspace.name);
+        }
+
+        // The sample arcs overlap, but the ring is still closed
+        let issues = validate_airspace(&airspaces[2]);
+        assert!(issues.iter().all(|issue| issue.rule != Rule::UnclosedRing));
+    }
+
+    #[test]
+    fn parse_counterclockwise_arc() {
+        let center = Point::lat_lon(0.8, 0.1);
+        let points = arc_points(center, 1000., 90_f32.to_radians(), 0., true);
+
+        assert_eq!(points.len(), 19);
+        // Halfway point should be to the north-east
+        let mid_bearing = center.bearing_to(points[9]).to_degrees();
+        assert_lt!((mid_bearing - 45.).abs(), 0.1);
+    }
+
+    #[test]
+    fn parse_altitudes() {
+        assert_eq!(
+            parse_altitude("SFC").unwrap(),
+            (0, AltStyle::AboveGroundLevel)
+        );
+        assert_eq!(
+            parse_altitude("GND").unwrap(),
+            (0, AltStyle::AboveGroundLevel)
+        );
+        assert_eq!(
+            parse_altitude("FL65").unwrap(),
+            (1981, AltStyle::FlightLevel)
+        );
+        assert_eq!(
+            parse_altitude("FL 195").unwrap(),
+            (5944, AltStyle::FlightLevel)
+        );
+        assert_eq!(
+            parse_altitude("2500ft AMSL").unwrap(),
+            (762, AltStyle::MeanSeaLevel)
+        );
+        assert_eq!(
+            parse_altitude("2500 MSL").unwrap(),
+            (762, AltStyle::MeanSeaLevel)
+        );
+        assert_eq!(
+            parse_altitude("2500F").unwrap(),
+            (762, AltStyle::MeanSeaLevel)
+        );
+        assert_eq!(
+            parse_altitude("2500").unwrap(),
+            (762, AltStyle::MeanSeaLevel)
+        );
+        assert_eq!(
+            parse_altitude("1500m").unwrap(),
+            (1500, AltStyle::MeanSeaLevel)
+        );
+        assert_eq!(
+            parse_altitude("1500 m MSL").unwrap(),
+            (1500, AltStyle::MeanSeaLevel)
+        );
+        assert_eq!(
+            parse_altitude("1000ft AGL").unwrap(),
+            (305, AltStyle::AboveGroundLevel)
+        );
+        assert_eq!(
+            parse_altitude("1000 GND").unwrap(),
+            (305, AltStyle::AboveGroundLevel)
+        );
+        assert_eq!(
+            parse_altitude("UNL").unwrap(),
+            (i16::MAX, AltStyle::Unlimited)
+        );
+        assert_eq!(parse_altitude("NOTAM").unwrap(), (0, AltStyle::Notam));
+
+        assert_err!(parse_altitude("high"));
+        assert_err!(parse_altitude("2500 furlongs"));
+        assert_err!(parse_altitude("FL9999"));
+    }
+
+    #[test]
+    fn parse_coordinates() {
+        let point = parse_coordinate("45:12:30 N 005:30:00 E").unwrap();
+        assert_lt!((point.lat.to_degrees() - 45.208_33).abs(), 1e-4);
+        assert_lt!((point.lon.to_degrees() - 5.5).abs(), 1e-4);
+
+        let point = parse_coordinate("45:12.5S 005:30.0W").unwrap();
+        assert_lt!((point.lat.to_degrees() + 45.208_33).abs(), 1e-4);
+        assert_lt!((point.lon.to_degrees() + 5.5).abs(), 1e-4);
+
+        let point = parse_coordinate("45.5 N 5.25 E").unwrap();
+        assert_lt!((point.lat.to_degrees() - 45.5).abs(), 1e-4);
+        assert_lt!((point.lon.to_degrees() - 5.25).abs(), 1e-4);
+
+        assert_err!(parse_coordinate("45:12:30 005:30:00 E"));
+        assert_err!(parse_coordinate("45:72:30 N 005:30:00 E"));
+        assert_err!(parse_coordinate("95:00:00 N 005:30:00 E"));
+    }
+
+    #[test]
+    fn parse_reports_line_numbers() {
+        let input = "AC R\nAN Test\nAL 1000 furlongs\n";
+        assert_matches!(parse(input), Err(Error::InvalidOpenAir { line: 3, .. }));
+
+        let input = "AN Orphan\n";
+        assert_matches!(parse(input), Err(Error::InvalidOpenAir { line: 1, .. }));
+
+        let input = "AC R\nDC 5\n";
+        assert_matches!(parse(input), Err(Error::InvalidOpenAir { line: 2, .. }));
+    }
+
+    #[test]
+    fn read_cp1252_input() {
+        // "AN Zürich" with CP1252 encoded "ü"
+        let input = b"AC D\nAN Z\xFCrich\n";
+        let airspaces = read(&input[..]).expect("Failed to read");
+        assert_eq!(airspaces[0].name, "Zürich");
+    }
+}
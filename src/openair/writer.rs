@@ -0,0 +1,274 @@
+use super::{CLASS_CODES, METERS_PER_FOOT, STYLE_CODES};
+use crate::error::Result;
+use crate::{Airspace, AltStyle, CubClass, CubStyle, Point};
+use std::io::Write;
+use std::path::Path;
+
+/// Line ending used for all written records
+///
+/// OpenAir files are traditionally created on Windows, and some flight
+/// instruments do not accept plain `\n` line endings.
+const LINE_ENDING: &str = "\r\n";
+
+/// Write airspaces as OpenAir text
+///
+/// Each airspace is written as a block of `AC`, `AN`, `AL`, `AH` and `DP`
+/// records, plus `AY` if both class and style are known and `AF`/`AG` if a
+/// frequency is set:
+///
+/// - If the `CubClass` is known, it is written as the `AC` letter and the
+///   `CubStyle` as an `AY` record (extended format). Otherwise the style is
+///   written as the `AC` code. Airspaces with neither are written as
+///   `AC UNC` (unclassified).
+/// - Altitudes are converted from meters to feet (rounded to 10 ft) or flight
+///   levels. A `0` AGL floor is written as `SFC`, `AltStyle::Unknown` is
+///   written like `AltStyle::MeanSeaLevel`.
+/// - Points are written in degrees/minutes/seconds.
+///
+/// # Returns
+///
+/// Ok(()) on success or an error if writing fails
+pub fn write<'a, W, I>(mut writer: W, airspaces: I) -> Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = &'a Airspace>,
+{
+    for (i, airspace) in airspaces.into_iter().enumerate() {
+        if i > 0 {
+            write!(writer, "{LINE_ENDING}")?;
+        }
+        write_airspace(&mut writer, airspace)?;
+    }
+
+    Ok(())
+}
+
+/// Write airspaces as OpenAir text to a file path
+///
+/// Convenience wrapper around `write()` that creates a file at the given path.
+pub fn write_to_path<'a, P, I>(path: P, airspaces: I) -> Result<()>
+where
+    P: AsRef<Path>,
+    I: IntoIterator<Item = &'a Airspace>,
+{
+    let file = std::fs::File::create(path)?;
+    let mut writer = std::io::BufWriter::new(file);
+    write(&mut writer, airspaces)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Write a single airspace block
+fn write_airspace<W: Write>(writer: &mut W, airspace: &Airspace) -> Result<()> {
+    let (ac, ay) = type_codes(airspace.style, airspace.class);
+    write!(writer, "AC {ac}{LINE_ENDING}")?;
+    if let Some(ay) = ay {
+        write!(writer, "AY {ay}{LINE_ENDING}")?;
+    }
+    write!(writer, "AN {}{LINE_ENDING}", airspace.name)?;
+
+    if let Some(frequency) = airspace.frequency {
+        write!(writer, "AF {frequency:.3}{LINE_ENDING}")?;
+    }
+    if let Some(frequency_name) = &airspace.frequency_name {
+        write!(writer, "AG {frequency_name}{LINE_ENDING}")?;
+    }
+
+    let lower = format_altitude(airspace.min_alt, airspace.min_alt_style);
+    let upper = format_altitude(airspace.max_alt, airspace.max_alt_style);
+    write!(writer, "AL {lower}{LINE_ENDING}")?;
+    write!(writer, "AH {upper}{LINE_ENDING}")?;
+
+    for point in &airspace.points {
+        write!(writer, "DP {}{LINE_ENDING}", format_coordinate(*point))?;
+    }
+
+    Ok(())
+}
+
+/// Get the OpenAir `AC` code and optional `AY` code for a style and class
+fn type_codes(style: CubStyle, class: CubClass) -> (&'static str, Option<&'static str>) {
+    let style_code = STYLE_CODES
+        .iter()
+        .find(|&&(_, s)| s == style)
+        .map(|&(code, _)| code);
+    let class_code = CLASS_CODES
+        .iter()
+        .find(|&&(_, c)| c == class)
+        .map(|&(code, _)| code);
+
+    match (class_code, style_code) {
+        (Some(class_code), style_code) => (class_code, style_code),
+        (None, Some(style_code)) => (style_code, None),
+        (None, None) => ("UNC", None),
+    }
+}
+
+/// Format an altitude in meters as an OpenAir altitude string
+fn format_altitude(alt: i16, style: AltStyle) -> String {
+    let feet = (alt as f32 / METERS_PER_FOOT / 10.).round() as i32 * 10;
+
+    match style {
+        AltStyle::AboveGroundLevel if alt == 0 => "SFC".to_string(),
+        AltStyle::AboveGroundLevel => format!("{feet}ft AGL"),
+        AltStyle::MeanSeaLevel | AltStyle::Unknown => format!("{feet}ft AMSL"),
+        AltStyle::FlightLevel => {
+            let level = (alt as f32 / METERS_PER_FOOT / 100.).round() as i32;
+            format!("FL{level}")
+        }
+        AltStyle::Unlimited => "UNL".to_string(),
+        AltStyle::Notam => "NOTAM".to_string(),
+    }
+}
+
+/// Format a point as `DD:MM:SS N DDD:MM:SS E`
+fn format_coordinate(point: Point) -> String {
+    let lat_hemisphere = if point.lat < 0. { 'S' } else { 'N' };
+    let lon_hemisphere = if point.lon < 0. { 'W' } else { 'E' };
+
+    let (lat_d, lat_m, lat_s) = to_dms(point.lat.to_degrees().abs());
+    let (lon_d, lon_m, lon_s) = to_dms(point.lon.to_degrees().abs());
+
+    format!(
+        "{lat_d:02}:{lat_m:02}:{lat_s:02} {lat_hemisphere} {lon_d:03}:{lon_m:02}:{lon_s:02} {lon_hemisphere}"
+    )
+}
+
+/// Split decimal degrees into whole degrees, minutes and rounded seconds
+fn to_dms(degrees: f32) -> (u32, u32, u32) {
+    let total_seconds = (degrees as f64 * 3600.).round() as u32;
+    (
+        total_seconds / 3600,
+        (total_seconds / 60) % 60,
+        total_seconds % 60,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CubReader;
+    use crate::openair::parse;
+    use claims::assert_lt;
+
+    #[test]
+    fn write_single_airspace() {
+        let airspace = Airspace {
+            name: "GRENOBLE CTR".to_string(),
+            style: CubStyle::ControlZone,
+            class: CubClass::ClassD,
+            frequency: Some(120.4),
+            frequency_name: Some("Grenoble Tower".to_string()),
+            min_alt: 0,
+            min_alt_style: AltStyle::AboveGroundLevel,
+            max_alt: 1981,
+            max_alt_style: AltStyle::FlightLevel,
+            points: vec![
+                Point::lat_lon(45.208_332_f32.to_radians(), 5.5_f32.to_radians()),
+                Point::lat_lon(45.25_f32.to_radians(), (-5.583_333_f32).to_radians()),
+                Point::lat_lon((-45.166_668_f32).to_radians(), 5.666_667_f32.to_radians()),
+            ],
+            ..Default::default()
+        };
+
+        let mut buf = Vec::new();
+        write(&mut buf, [&airspace]).expect("Failed to write");
+
+        let text = String::from_utf8(buf).unwrap();
+        assert_eq!(
+            text,
+            "AC D\r\n\
+             AY CTR\r\n\
+             AN GRENOBLE CTR\r\n\
+             AF 120.400\r\n\
+             AG Grenoble Tower\r\n\
+             AL SFC\r\n\
+             AH FL65\r\n\
+             DP 45:12:30 N 005:30:00 E\r\n\
+             DP 45:15:00 N 005:35:00 W\r\n\
+             DP 45:10:00 S 005:40:00 E\r\n"
+        );
+    }
+
+    #[test]
+    fn type_codes_for_style_and_class() {
+        assert_eq!(
+            type_codes(CubStyle::RestrictedArea, CubClass::Unknown),
+            ("R", None)
+        );
+        assert_eq!(
+            type_codes(CubStyle::DangerArea, CubClass::Unknown),
+            ("Q", None)
+        );
+        assert_eq!(
+            type_codes(CubStyle::ProhibitedArea, CubClass::Unknown),
+            ("P", None)
+        );
+        assert_eq!(
+            type_codes(CubStyle::GliderSector, CubClass::Unknown),
+            ("GSEC", None)
+        );
+        assert_eq!(type_codes(CubStyle::Unknown, CubClass::ClassC), ("C", None));
+        assert_eq!(
+            type_codes(CubStyle::ControlZone, CubClass::ClassD),
+            ("D", Some("CTR"))
+        );
+        assert_eq!(
+            type_codes(CubStyle::Unknown, CubClass::Unknown),
+            ("UNC", None)
+        );
+    }
+
+    #[test]
+    fn format_altitudes() {
+        assert_eq!(format_altitude(0, AltStyle::AboveGroundLevel), "SFC");
+        assert_eq!(
+            format_altitude(305, AltStyle::AboveGroundLevel),
+            "1000ft AGL"
+        );
+        assert_eq!(format_altitude(762, AltStyle::MeanSeaLevel), "2500ft AMSL");
+        assert_eq!(format_altitude(5944, AltStyle::FlightLevel), "FL195");
+        assert_eq!(format_altitude(i16::MAX, AltStyle::Unlimited), "UNL");
+        assert_eq!(format_altitude(0, AltStyle::Notam), "NOTAM");
+    }
+
+    #[test]
+    fn format_coordinate_carries_rounded_seconds() {
+        // 45°59'59.9" should round up to 46°00'00"
+        let degrees = 45. + 59. / 60. + 59.9 / 3600.;
+        let point = Point::lat_lon(f32::to_radians(degrees), 0.);
+        assert_eq!(format_coordinate(point), "46:00:00 N 000:00:00 E");
+    }
+
+    #[test]
+    fn round_trip_france_fixture() {
+        let original: Vec<_> = CubReader::from_path("tests/fixtures/france_2024.07.02.cub")
+            .expect("Failed to open fixture")
+            .read_airspaces()
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+
+        let mut buf = Vec::new();
+        write(&mut buf, &original).expect("Failed to write");
+
+        let read_back = parse(&String::from_utf8(buf).unwrap()).expect("Failed to parse");
+        assert_eq!(read_back.len(), original.len());
+
+        for (original, read_back) in original.iter().zip(&read_back) {
+            // Trailing whitespace is not preserved by the line-based format
+            assert_eq!(read_back.name, original.name.trim_end());
+            assert_eq!(read_back.style, original.style);
+            assert_eq!(read_back.class, original.class);
+            assert_eq!(read_back.min_alt_style, original.min_alt_style);
+            assert_eq!(read_back.max_alt_style, original.max_alt_style);
+            assert_lt!((read_back.min_alt - original.min_alt).abs(), 16);
+            assert_lt!((read_back.max_alt - original.max_alt).abs(), 16);
+            assert_eq!(read_back.points.len(), original.points.len());
+
+            // One arc second is about 31 meters
+            for (a, b) in original.points.iter().zip(&read_back.points) {
+                assert_lt!(a.distance_to(*b), 25.);
+            }
+        }
+    }
+}
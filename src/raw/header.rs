@@ -24,6 +24,11 @@ const MIN_SIZE_OF_ITEM: i32 = 26;
 /// Minimum accepted `size_of_point` (defined by the spec).
 const MIN_SIZE_OF_POINT: i32 = 5;
 
+/// Bit in the reserved `Alignment` field that marks `Crc32` as computed.
+///
+/// This is an extension of this crate, since the spec does not define a CRC.
+const ALIGNMENT_CRC_FLAG: i32 = 0x1;
+
 /// CUB file header (first 210 bytes)
 ///
 /// Contains metadata about the airspace file including bounding box,
@@ -34,6 +39,10 @@ pub struct Header {
     pub title: ByteString,
     pub allowed_serials: [u16; 8],
     pub pc_byte_order: u8,
+    /// CRC32 of everything following the header (items and item data)
+    ///
+    /// Only valid if `has_crc32()` returns `true`, see `set_crc32()`.
+    pub crc32: u32,
     pub key: [u8; ENCRYPTION_KEY_SIZE],
     pub size_of_item: i32,
     pub size_of_point: i32,
@@ -44,6 +53,8 @@ pub struct Header {
     pub max_height: f32,
     pub lo_la_scale: f32,
     pub data_offset: i32,
+    /// Reserved `Alignment` field, bit 0 marks `crc32` as valid
+    pub alignment: i32,
 }
 
 impl Header {
@@ -99,7 +110,7 @@ impl Header {
         }
 
         // Read Crc32 (offset 134-137)
-        let _crc32 = read_u32(reader, byte_order)?;
+        let crc32 = read_u32(reader, byte_order)?;
 
         // Read Key (offset 138-153, 16 bytes)
         let key = {
@@ -127,7 +138,7 @@ impl Header {
         }
 
         let data_offset = read_i32(reader, byte_order)?;
-        let _alignment = read_i32(reader, byte_order)?;
+        let alignment = read_i32(reader, byte_order)?;
 
         if size_of_item < MIN_SIZE_OF_ITEM {
             return Err(Error::UndersizedItems { size_of_item });
@@ -141,6 +152,7 @@ impl Header {
             title,
             allowed_serials,
             pc_byte_order,
+            crc32,
             key,
             size_of_item,
             size_of_point,
@@ -151,6 +163,7 @@ impl Header {
             max_height,
             lo_la_scale,
             data_offset,
+            alignment,
         };
 
         Ok(header)
@@ -166,6 +179,24 @@ impl Header {
         ByteOrder::from_pc_byte_order(self.pc_byte_order)
     }
 
+    /// Check if `crc32` holds a computed checksum
+    ///
+    /// This is the case if the CRC flag in the `alignment` field is set, so
+    /// that a computed checksum of `0` can be told apart from no checksum.
+    pub fn has_crc32(&self) -> bool {
+        self.alignment & ALIGNMENT_CRC_FLAG != 0
+    }
+
+    /// Set or clear the computed checksum and the CRC flag
+    pub fn set_crc32(&mut self, crc32: Option<u32>) {
+        self.crc32 = crc32.unwrap_or(0);
+        if crc32.is_some() {
+            self.alignment |= ALIGNMENT_CRC_FLAG;
+        } else {
+            self.alignment &= !ALIGNMENT_CRC_FLAG;
+        }
+    }
+
     /// Write CUB file header to writer
     ///
     /// Writes exactly 210 bytes to the writer.
@@ -198,7 +229,7 @@ impl Header {
         write_u8(writer, 0)?; // Always 0 (not encrypted)
 
         // Write Crc32 (offset 134-137)
-        write_u32(writer, 0, byte_order)?; // CRC not implemented
+        write_u32(writer, self.crc32, byte_order)?;
 
         // Write Key (offset 138-153, 16 bytes)
         writer.write_all(&self.key)?;
@@ -219,7 +250,7 @@ impl Header {
         write_i32(writer, self.data_offset, byte_order)?;
 
         // Write alignment (offset 206-209)
-        write_i32(writer, 0, byte_order)?; // Alignment field (ignored)
+        write_i32(writer, self.alignment, byte_order)?;
 
         Ok(HEADER_SIZE)
     }
@@ -247,6 +278,7 @@ mod tests {
             title: ByteString::from(b"Test Airspace".to_vec()),
             allowed_serials: [1, 2, 3, 4, 5, 6, 7, 8],
             pc_byte_order: 0, // LE
+            crc32: 0xDEADBEEF,
             key: [0; 16],
             size_of_item: 42,
             size_of_point: 5,
@@ -262,6 +294,7 @@ mod tests {
             max_height: 2.0,
             lo_la_scale: 1000.0,
             data_offset: 630,
+            alignment: ALIGNMENT_CRC_FLAG,
         };
 
         // Write to buffer
@@ -274,6 +307,24 @@ mod tests {
         let read_back = Header::read(&mut cursor).expect("Failed to read header");
 
         assert_eq!(read_back, original);
+        assert!(read_back.has_crc32());
+    }
+
+    #[test]
+    fn crc32_flag() {
+        let mut header =
+            Header::read(&mut File::open("tests/fixtures/france_2024.07.02.cub").unwrap())
+                .expect("Failed to read header");
+        assert!(!header.has_crc32());
+
+        // A computed checksum of 0 is distinct from no checksum
+        header.set_crc32(Some(0));
+        assert!(header.has_crc32());
+        assert_eq!(header.crc32, 0);
+
+        header.set_crc32(None);
+        assert!(!header.has_crc32());
+        assert_eq!(header.alignment, 0);
     }
 
     #[test]
@@ -283,6 +334,7 @@ mod tests {
             title: ByteString::from(b"BE Test".to_vec()),
             allowed_serials: [1, 2, 3, 4, 5, 6, 7, 8],
             pc_byte_order: 1, // BE
+            crc32: 0,
             key: [0xFF; 16],
             size_of_item: 43,
             size_of_point: 5,
@@ -298,6 +350,7 @@ mod tests {
             max_height: 5.0,
             lo_la_scale: 2000.0,
             data_offset: 4510,
+            alignment: 0,
         };
 
         // Write and read back
@@ -316,6 +369,7 @@ mod tests {
             title: ByteString::from(vec![]),
             allowed_serials: [0; 8],
             pc_byte_order: 0,
+            crc32: 0,
             key: [0; 16],
             size_of_item: 26,
             size_of_point: 5,
@@ -331,6 +385,7 @@ mod tests {
             max_height: 0.0,
             lo_la_scale: 1.0,
             data_offset: 210,
+            alignment: 0,
         };
 
         // Write and read back
@@ -350,6 +405,7 @@ mod tests {
             title: ByteString::from(long_title.clone()),
             allowed_serials: [0; 8],
             pc_byte_order: 0,
+            crc32: 0,
             key: [0; 16],
             size_of_item: 43,
             size_of_point: 5,
@@ -365,6 +421,7 @@ mod tests {
             max_height: 0.0,
             lo_la_scale: 1.0,
             data_offset: 253,
+            alignment: 0,
         };
 
         // Write and read back
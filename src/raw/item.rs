@@ -1,12 +1,13 @@
 use crate::error::Result;
-use crate::raw::Header;
+use crate::raw::{Header, NotamTime};
+use crate::types::activity_at;
 use crate::utils::io::{
     read_i16, read_i32, read_u8, read_u32, read_u64, write_i16, write_i32, write_u8, write_u32,
     write_u64,
 };
 use crate::{
-    AltStyle, BoundingBox, CubClass, CubStyle, DateTime, DaysActive, ExtendedType, NotamCodes,
-    NotamScope, NotamTraffic, NotamType,
+    Activity, AltStyle, BoundingBox, CubClass, CubStyle, DateTime, DaysActive, ExtendedType,
+    NotamCodes, NotamInfo, NotamScope, NotamTraffic, NotamType,
 };
 use std::io::{Cursor, Read, Write};
 
@@ -126,6 +127,34 @@ impl Item {
         ExtendedType::from_byte(self.extended_type_byte)
     }
 
+    /// Set airspace style/type (inverse of `style()`)
+    ///
+    /// Only touches the lowest 4 bits and the highest bit of the type byte,
+    /// leaving the class untouched.
+    pub fn set_style(&mut self, style: CubStyle) {
+        self.type_byte = (self.type_byte & 0x70) | style.as_nibble();
+    }
+
+    /// Set airspace class (inverse of `class()`)
+    ///
+    /// Only touches bits 4-6 of the type byte, leaving the style untouched.
+    pub fn set_class(&mut self, class: CubClass) {
+        self.type_byte = (self.type_byte & 0x8F) | (class.as_nibble() << 4);
+    }
+
+    /// Set minimum and maximum altitude styles (inverse of `min_alt_style()`
+    /// and `max_alt_style()`)
+    pub fn set_alt_styles(&mut self, min: AltStyle, max: AltStyle) {
+        self.alt_style_byte = (max.as_nibble() << 4) | min.as_nibble();
+    }
+
+    /// Set extended type (inverse of `extended_type()`)
+    ///
+    /// `None` clears the extended type byte.
+    pub fn set_extended_type(&mut self, extended_type: Option<ExtendedType>) {
+        self.extended_type_byte = extended_type.map_or(0, |t| t.as_byte());
+    }
+
     /// Get active days flags
     pub fn days_active(&self) -> DaysActive {
         let bits = ((self.active_time >> 52) & 0xFFF) as u16;
@@ -138,7 +167,7 @@ impl Item {
         if value == 0 {
             None
         } else {
-            Some(decode_notam_time(value))
+            Some(NotamTime::from_raw(value).decode_unchecked())
         }
     }
 
@@ -148,10 +177,22 @@ impl Item {
         if value == 0x3FFFFFF {
             None
         } else {
-            Some(decode_notam_time(value))
+            Some(NotamTime::from_raw(value).decode_unchecked())
         }
     }
 
+    /// Check if the airspace is active at the given time (UTC)
+    ///
+    /// See `Airspace::is_active_at()` for details.
+    pub fn is_active_at(&self, time: DateTime) -> Activity {
+        activity_at(
+            self.start_date().as_ref(),
+            self.end_date().as_ref(),
+            &self.days_active(),
+            &time,
+        )
+    }
+
     /// Check if ExtraData contains NOTAM data
     pub fn has_notam_data(&self) -> bool {
         (self.extra_data >> 30) == 0 && self.extra_data != 0
@@ -189,6 +230,21 @@ impl Item {
         NotamCodes::from_extra_data(self.extra_data)
     }
 
+    /// Get all NOTAM information if ExtraData contains losslessly decodable
+    /// NOTAM data
+    pub fn notam_info(&self) -> Option<NotamInfo> {
+        NotamInfo::from_extra_data(self.extra_data)
+    }
+
+    /// Set NOTAM information (inverse of `notam_info()`)
+    ///
+    /// `None` clears the ExtraData field. Returns `Error::InvalidNotam` and
+    /// leaves the field unchanged if the NOTAM codes can't be encoded.
+    pub fn set_notam_info(&mut self, notam_info: Option<NotamInfo>) -> Result<()> {
+        self.extra_data = notam_info.map_or(Ok(0), |info| info.to_extra_data())?;
+        Ok(())
+    }
+
     /// Get bounding box
     pub fn bounding_box(&self) -> &BoundingBox {
         &self.bounding_box
@@ -245,32 +301,11 @@ impl Item {
     }
 }
 
-/// Decode NOTAM time from encoded minutes to DateTime
-pub fn decode_notam_time(encoded: u32) -> DateTime {
-    let mut time = encoded;
-    let minute = (time % 60) as u8;
-    time /= 60;
-    let hour = (time % 24) as u8;
-    time /= 24;
-    let day = (time % 31) as u8 + 1;
-    time /= 31;
-    let month = (time % 12) as u8 + 1;
-    time /= 12;
-    let year = time + 2000;
-
-    DateTime {
-        day,
-        month,
-        year,
-        hour,
-        minute,
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::raw::HEADER_SIZE;
+    use claims::assert_ok;
     use std::fs::File;
     use std::io::{Seek, SeekFrom};
 
@@ -323,16 +358,67 @@ mod tests {
     }
 
     #[test]
-    fn decode_notam_time_example() {
-        // Example: 2024-07-15 14:30
-        // Manually calculated encoded value
-        let encoded = 30 + 60 * (14 + 24 * (14 + 31 * (6 + 12 * 24)));
-        let dt = decode_notam_time(encoded);
-        assert_eq!(dt.year, 2024);
-        assert_eq!(dt.month, 7);
-        assert_eq!(dt.day, 15);
-        assert_eq!(dt.hour, 14);
-        assert_eq!(dt.minute, 30);
+    fn setters_are_inverse_of_getters() {
+        let mut item = Item {
+            bounding_box: BoundingBox {
+                left: 0.0,
+                top: 0.0,
+                right: 0.0,
+                bottom: 0.0,
+            },
+            type_byte: 0,
+            alt_style_byte: 0,
+            min_alt: 0,
+            max_alt: 0,
+            points_offset: 0,
+            extra_data: 0,
+            active_time: 0,
+            extended_type_byte: 0,
+        };
+
+        for style in CubStyle::ALL {
+            for class in CubClass::ALL {
+                item.set_style(style);
+                item.set_class(class);
+                assert_eq!(item.style(), style);
+                assert_eq!(item.class(), class);
+
+                // Setting the style again must not clobber the class
+                item.set_style(style);
+                assert_eq!(item.class(), class);
+            }
+        }
+
+        for min in AltStyle::ALL {
+            for max in AltStyle::ALL {
+                item.set_alt_styles(min, max);
+                assert_eq!(item.min_alt_style(), min);
+                assert_eq!(item.max_alt_style(), max);
+            }
+        }
+
+        for extended_type in ExtendedType::ALL.map(Some).into_iter().chain([None]) {
+            item.set_extended_type(extended_type);
+            assert_eq!(item.extended_type(), extended_type);
+        }
+
+        let notam_info = NotamInfo {
+            notam_type: NotamType::Cancel,
+            codes: Some(NotamCodes {
+                subject: ('M', 'R'),
+                action: ('L', 'C'),
+            }),
+            traffic: NotamTraffic::Ifr,
+            scope: NotamScope::AerodromeAndEnRoute,
+        };
+        assert_ok!(item.set_notam_info(Some(notam_info)));
+        assert_eq!(item.notam_info(), Some(notam_info));
+        assert_eq!(item.notam_type(), Some(NotamType::Cancel));
+        assert_eq!(item.notam_codes(), notam_info.codes);
+
+        assert_ok!(item.set_notam_info(None));
+        assert_eq!(item.notam_info(), None);
+        assert!(!item.has_notam_data());
     }
 
     #[test]
@@ -358,6 +444,7 @@ mod tests {
             title: crate::utils::ByteString::from(vec![]),
             allowed_serials: [0; 8],
             pc_byte_order: 0, // LE
+            crc32: 0,
             key: [0; 16],
             size_of_item: 43, // Full item size
             size_of_point: 5,
@@ -373,6 +460,7 @@ mod tests {
             max_height: 0.0,
             lo_la_scale: 1000.0,
             data_offset: 253,
+            alignment: 0,
         };
 
         // Create an item with known values
@@ -414,6 +502,7 @@ mod tests {
             title: crate::utils::ByteString::from(vec![]),
             allowed_serials: [0; 8],
             pc_byte_order: 1, // BE
+            crc32: 0,
             key: [0; 16],
             size_of_item: 43,
             size_of_point: 5,
@@ -429,6 +518,7 @@ mod tests {
             max_height: 0.0,
             lo_la_scale: 1000.0,
             data_offset: 253,
+            alignment: 0,
         };
 
         let original = Item {
@@ -464,6 +554,7 @@ mod tests {
             title: crate::utils::ByteString::from(vec![]),
             allowed_serials: [0; 8],
             pc_byte_order: 0,
+            crc32: 0,
             key: [0; 16],
             size_of_item: 50,
             size_of_point: 5,
@@ -479,6 +570,7 @@ mod tests {
             max_height: 0.0,
             lo_la_scale: 1000.0,
             data_offset: 260,
+            alignment: 0,
         };
 
         let original = Item {
@@ -18,28 +18,31 @@ const NAME_LENGTH_MASK: u8 = 0x3F;
 /// - Point operations are raw i16 offsets (not yet converted to lat/lon)
 /// - Strings are raw bytes (not yet decoded from UTF-8/Extended ASCII)
 /// - Optional attributes remain as raw bytes for maximum flexibility
+///
+/// String fields are `ByteString`s by default. `CubSlice` returns
+/// `ItemData<&[u8]>` instead, which borrows the strings from the file contents.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ItemData {
+pub struct ItemData<S = ByteString> {
     /// Raw point operations (origin moves and new points with i16 x/y offsets)
     pub point_ops: Vec<PointOp>,
 
     /// Airspace name (raw bytes, not decoded)
-    pub name: Option<ByteString>,
+    pub name: Option<S>,
     /// Primary frequency in Hz
     pub frequency: Option<u32>,
     /// Primary frequency name/label (raw bytes, not decoded)
-    pub frequency_name: Option<ByteString>,
+    pub frequency_name: Option<S>,
     /// ICAO code (raw bytes, not decoded)
-    pub icao_code: Option<ByteString>,
+    pub icao_code: Option<S>,
     /// Secondary frequency in Hz
     pub secondary_frequency: Option<u32>,
     /// Class exception rules (raw bytes, not decoded)
-    pub exception_rules: Option<ByteString>,
+    pub exception_rules: Option<S>,
     /// NOTAM remarks (raw bytes, not decoded)
-    pub notam_remarks: Option<ByteString>,
+    pub notam_remarks: Option<S>,
     /// NOTAM identifier (raw bytes, not decoded)
-    pub notam_id: Option<ByteString>,
-    /// NOTAM insert time (raw encoded value)
+    pub notam_id: Option<S>,
+    /// NOTAM insert time (raw encoded value, see `NotamTime`)
     pub notam_insert_time: Option<u32>,
 }
 
@@ -57,55 +60,7 @@ impl ItemData {
     ///
     /// The parsed `ItemData` or an error if reading fails
     pub fn read<R: Read>(reader: &mut R, header: &Header) -> Result<Self> {
-        let byte_order = header.byte_order();
-
-        let mut item_data = Self {
-            point_ops: Vec::with_capacity(4),
-            name: None,
-            frequency: None,
-            frequency_name: None,
-            icao_code: None,
-            secondary_frequency: None,
-            exception_rules: None,
-            notam_remarks: None,
-            notam_id: None,
-            notam_insert_time: None,
-        };
-
-        loop {
-            let flag = match read_u8(reader) {
-                Ok(flag) => flag,
-                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
-                    return Ok(item_data);
-                }
-                Err(e) => return Err(e.into()),
-            };
-
-            match flag {
-                POINT_OP_MOVE_ORIGIN => {
-                    // Origin update
-                    let x = read_i16(reader, byte_order)?;
-                    let y = read_i16(reader, byte_order)?;
-                    item_data.point_ops.push(PointOp::MoveOrigin { x, y });
-                }
-
-                POINT_OP_NEW_POINT => {
-                    // Geometry point
-                    let x = read_i16(reader, byte_order)?;
-                    let y = read_i16(reader, byte_order)?;
-                    item_data.point_ops.push(PointOp::NewPoint { x, y });
-                }
-
-                flag if (flag & ATTR_NAME_FLAG) != 0 => {
-                    // Attributes section - parse and return
-                    return parse_attributes(reader, header, flag, item_data);
-                }
-
-                _ => {
-                    return Err(Error::UnexpectedPointFlag(flag));
-                }
-            }
-        }
+        read_item_data(&mut OwnedStrings(reader), header).map(|(item_data, _)| item_data)
     }
 
     /// Write item data to writer
@@ -247,13 +202,123 @@ impl ItemData {
     }
 }
 
+/// Source of item data that determines how strings are stored
+pub(crate) trait StringReader: Read {
+    type String;
+
+    /// Read a string of `len` bytes
+    fn read_string(&mut self, len: usize) -> std::io::Result<Self::String>;
+}
+
+/// Reads strings into newly allocated `ByteString`s
+pub(crate) struct OwnedStrings<'r, R>(pub &'r mut R);
+
+impl<R: Read> Read for OwnedStrings<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl<R: Read> StringReader for OwnedStrings<'_, R> {
+    type String = ByteString;
+
+    fn read_string(&mut self, len: usize) -> std::io::Result<ByteString> {
+        ByteString::read(self.0, len)
+    }
+}
+
+/// Borrows strings from the underlying byte slice
+pub(crate) struct BorrowedStrings<'a>(pub &'a [u8]);
+
+impl Read for BorrowedStrings<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl<'a> StringReader for BorrowedStrings<'a> {
+    type String = &'a [u8];
+
+    fn read_string(&mut self, len: usize) -> std::io::Result<&'a [u8]> {
+        if len > self.0.len() {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+
+        let (string, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(string)
+    }
+}
+
+/// Read raw item data and the flag that terminated the attribute section
+///
+/// The attribute section has no explicit length. Parsing stops at the end of
+/// the input or at the first flag that does not start a known attribute,
+/// which is usually the first point operation of the next item. If parsing
+/// stopped at a flag that is neither a known attribute nor a point operation,
+/// that flag is returned so that callers can report it.
+pub(crate) fn read_item_data<R: StringReader>(
+    reader: &mut R,
+    header: &Header,
+) -> Result<(ItemData<R::String>, Option<u8>)> {
+    let byte_order = header.byte_order();
+
+    let mut item_data = ItemData {
+        point_ops: Vec::with_capacity(4),
+        name: None,
+        frequency: None,
+        frequency_name: None,
+        icao_code: None,
+        secondary_frequency: None,
+        exception_rules: None,
+        notam_remarks: None,
+        notam_id: None,
+        notam_insert_time: None,
+    };
+
+    loop {
+        let flag = match read_u8(reader) {
+            Ok(flag) => flag,
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                return Ok((item_data, None));
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        match flag {
+            POINT_OP_MOVE_ORIGIN => {
+                // Origin update
+                let x = read_i16(reader, byte_order)?;
+                let y = read_i16(reader, byte_order)?;
+                item_data.point_ops.push(PointOp::MoveOrigin { x, y });
+            }
+
+            POINT_OP_NEW_POINT => {
+                // Geometry point
+                let x = read_i16(reader, byte_order)?;
+                let y = read_i16(reader, byte_order)?;
+                item_data.point_ops.push(PointOp::NewPoint { x, y });
+            }
+
+            flag if (flag & ATTR_NAME_FLAG) != 0 => {
+                // Attributes section - parse and return
+                return parse_attributes(reader, header, flag, item_data);
+            }
+
+            _ => {
+                return Err(Error::UnexpectedPointFlag(flag));
+            }
+        }
+    }
+}
+
 /// Parse attribute section starting with given flag
-fn parse_attributes<R: Read>(
+fn parse_attributes<R: StringReader>(
     reader: &mut R,
     header: &Header,
     first_flag: u8,
-    mut item_data: ItemData,
-) -> Result<ItemData> {
+    mut item_data: ItemData<R::String>,
+) -> Result<(ItemData<R::String>, Option<u8>)> {
     let byte_order = header.byte_order();
 
     // First attribute: name
@@ -265,7 +330,7 @@ fn parse_attributes<R: Read>(
 
         let name_len = (first_flag & NAME_LENGTH_MASK) as usize;
         if name_len > 0 {
-            item_data.name = Some(ByteString::read(reader, name_len)?);
+            item_data.name = Some(reader.read_string(name_len)?);
         }
     }
 
@@ -274,7 +339,7 @@ fn parse_attributes<R: Read>(
         let flag = match read_u8(reader) {
             Ok(flag) => flag,
             Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
-                return Ok(item_data);
+                return Ok((item_data, None));
             }
             Err(e) => return Err(e.into()),
         };
@@ -286,7 +351,7 @@ fn parse_attributes<R: Read>(
                 item_data.frequency = Some(read_u32(reader, byte_order)?);
 
                 if freq_name_len > 0 {
-                    item_data.frequency_name = Some(ByteString::read(reader, freq_name_len)?);
+                    item_data.frequency_name = Some(reader.read_string(freq_name_len)?);
                 }
             }
 
@@ -294,16 +359,24 @@ fn parse_attributes<R: Read>(
                 parse_optional_data_record(reader, &mut item_data)?;
             }
 
+            POINT_OP_MOVE_ORIGIN | POINT_OP_NEW_POINT => {
+                // Start of the next item, stop parsing
+                return Ok((item_data, None));
+            }
+
             _ => {
                 // Unknown flag, stop parsing
-                return Ok(item_data);
+                return Ok((item_data, Some(flag)));
             }
         }
     }
 }
 
 /// Parse single optional data record
-fn parse_optional_data_record<R: Read>(reader: &mut R, item_data: &mut ItemData) -> Result<()> {
+fn parse_optional_data_record<R: StringReader>(
+    reader: &mut R,
+    item_data: &mut ItemData<R::String>,
+) -> Result<()> {
     let data_id = read_u8(reader)?;
     let b1 = read_u8(reader)?;
     let b2 = read_u8(reader)?;
@@ -312,7 +385,7 @@ fn parse_optional_data_record<R: Read>(reader: &mut R, item_data: &mut ItemData)
     match CubDataId::from_byte(data_id) {
         Some(CubDataId::IcaoCode) => {
             let len = b3 as usize;
-            item_data.icao_code = Some(ByteString::read(reader, len)?);
+            item_data.icao_code = Some(reader.read_string(len)?);
         }
 
         Some(CubDataId::SecondaryFrequency) => {
@@ -322,17 +395,17 @@ fn parse_optional_data_record<R: Read>(reader: &mut R, item_data: &mut ItemData)
 
         Some(CubDataId::ExceptionRules) => {
             let len = (((b2 as u16) << 8) | (b3 as u16)) as usize;
-            item_data.exception_rules = Some(ByteString::read(reader, len)?);
+            item_data.exception_rules = Some(reader.read_string(len)?);
         }
 
         Some(CubDataId::NotamRemarks) => {
             let len = (((b2 as u16) << 8) | (b3 as u16)) as usize;
-            item_data.notam_remarks = Some(ByteString::read(reader, len)?);
+            item_data.notam_remarks = Some(reader.read_string(len)?);
         }
 
         Some(CubDataId::NotamId) => {
             let len = b3 as usize;
-            item_data.notam_id = Some(ByteString::read(reader, len)?);
+            item_data.notam_id = Some(reader.read_string(len)?);
         }
 
         Some(CubDataId::NotamInsertTime) => {
@@ -389,6 +462,7 @@ mod tests {
             title: ByteString::from(b"Test".to_vec()),
             allowed_serials: [0; 8],
             pc_byte_order: 1, // LE
+            crc32: 0,
             key: [0; 16],
             size_of_item: 42,
             size_of_point: 5,
@@ -404,6 +478,7 @@ mod tests {
             max_height: 0.0,
             lo_la_scale: 0.0001,
             data_offset: 0,
+            alignment: 0,
         };
 
         // Build byte stream with all optional fields
@@ -504,6 +579,7 @@ mod tests {
             title: ByteString::from(vec![]),
             allowed_serials: [0; 8],
             pc_byte_order: 0, // LE
+            crc32: 0,
             key: [0; 16],
             size_of_item: 43,
             size_of_point: 5,
@@ -519,6 +595,7 @@ mod tests {
             max_height: 0.0,
             lo_la_scale: 1000.0,
             data_offset: 253,
+            alignment: 0,
         };
 
         // Create item data with all fields populated
@@ -561,6 +638,7 @@ mod tests {
             title: ByteString::from(vec![]),
             allowed_serials: [0; 8],
             pc_byte_order: 1, // BE
+            crc32: 0,
             key: [0; 16],
             size_of_item: 43,
             size_of_point: 5,
@@ -576,6 +654,7 @@ mod tests {
             max_height: 0.0,
             lo_la_scale: 1000.0,
             data_offset: 253,
+            alignment: 0,
         };
 
         let original = ItemData {
@@ -609,6 +688,7 @@ mod tests {
             title: ByteString::from(vec![]),
             allowed_serials: [0; 8],
             pc_byte_order: 0,
+            crc32: 0,
             key: [0; 16],
             size_of_item: 43,
             size_of_point: 5,
@@ -624,6 +704,7 @@ mod tests {
             max_height: 0.0,
             lo_la_scale: 1000.0,
             data_offset: 253,
+            alignment: 0,
         };
 
         // Only point operations, no optional fields
@@ -655,6 +736,7 @@ mod tests {
             title: ByteString::from(vec![]),
             allowed_serials: [0; 8],
             pc_byte_order: 0,
+            crc32: 0,
             key: [0; 16],
             size_of_item: 43,
             size_of_point: 5,
@@ -670,6 +752,7 @@ mod tests {
             max_height: 0.0,
             lo_la_scale: 1000.0,
             data_offset: 253,
+            alignment: 0,
         };
 
         // Create strings at maximum lengths
@@ -705,6 +788,7 @@ mod tests {
             title: ByteString::from(vec![]),
             allowed_serials: [0; 8],
             pc_byte_order: 0,
+            crc32: 0,
             key: [0; 16],
             size_of_item: 43,
             size_of_point: 5,
@@ -720,6 +804,7 @@ mod tests {
             max_height: 0.0,
             lo_la_scale: 1000.0,
             data_offset: 253,
+            alignment: 0,
         };
 
         // Create various point operation patterns
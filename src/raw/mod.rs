@@ -3,13 +3,20 @@
 //! This module provides direct access to CUB file components with minimal
 //! transformation. All functions read from the current cursor position
 //! without seeking. Users must manage file positioning themselves.
+//!
+//! Since these functions do not know where in the file they are reading from,
+//! their errors carry no location. Use `Error::with_context()` to attach the
+//! item index and byte offset, like `CubReader` does.
 
 mod header;
 mod item;
 mod item_data;
+mod notam_time;
 mod point_op;
 
 pub use self::header::{FILE_IDENTIFIER, HEADER_SIZE, Header};
 pub use self::item::Item;
 pub use self::item_data::ItemData;
+pub(crate) use self::item_data::{BorrowedStrings, OwnedStrings, read_item_data};
+pub use self::notam_time::NotamTime;
 pub use self::point_op::PointOp;
@@ -0,0 +1,176 @@
+use crate::DateTime;
+use crate::error::{Error, Result};
+
+/// Encoded NOTAM time as stored in CUB files
+///
+/// CUB files store dates as minutes since 2000-01-01 00:00 UTC, assuming
+/// that every month has 31 days. This format is used for the start and end
+/// dates in `Item::active_time` (26 bits each) and for
+/// `ItemData::notam_insert_time` (32 bits).
+///
+/// ```
+/// use seeyou_cub::DateTime;
+/// use seeyou_cub::raw::NotamTime;
+///
+/// let date_time = DateTime::new(2024, 7, 15, 14, 30)?;
+/// let encoded = NotamTime::encode(date_time)?;
+/// assert_eq!(encoded.to_raw(), 30 + 60 * (14 + 24 * (14 + 31 * (6 + 12 * 24))));
+/// assert_eq!(encoded.decode()?, date_time);
+/// # Ok::<(), seeyou_cub::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NotamTime(u32);
+
+impl NotamTime {
+    /// Largest value that fits into the 26 bit start and end date fields
+    pub const MAX_ACTIVE_TIME: u32 = 0x3FFFFFF;
+
+    pub fn from_raw(raw: u32) -> Self {
+        Self(raw)
+    }
+
+    pub fn to_raw(self) -> u32 {
+        self.0
+    }
+
+    /// Encode a date and time
+    ///
+    /// Returns `Error::InvalidDateTime` if `date_time` is not valid (see
+    /// `DateTime::is_valid()`). All valid values fit into the 26 bit start
+    /// and end date fields.
+    pub fn encode(date_time: DateTime) -> Result<Self> {
+        if !date_time.is_valid() {
+            return Err(Error::InvalidDateTime(date_time.to_string()));
+        }
+
+        let year = date_time.year - DateTime::MIN_YEAR;
+        let month = (date_time.month - 1) as u32;
+        let day = (date_time.day - 1) as u32;
+        let hour = date_time.hour as u32;
+        let minute = date_time.minute as u32;
+
+        Ok(Self(
+            minute + 60 * (hour + 24 * (day + 31 * (month + 12 * year))),
+        ))
+    }
+
+    /// Decode into a date and time
+    ///
+    /// Returns `Error::InvalidDateTime` if the result is not valid, e.g.
+    /// February 31.
+    pub fn decode(self) -> Result<DateTime> {
+        let date_time = self.decode_unchecked();
+        if !date_time.is_valid() {
+            return Err(Error::InvalidDateTime(date_time.to_string()));
+        }
+
+        Ok(date_time)
+    }
+
+    /// Decode into a date and time without checking that it is valid
+    pub fn decode_unchecked(self) -> DateTime {
+        let mut time = self.0;
+        let minute = (time % 60) as u8;
+        time /= 60;
+        let hour = (time % 24) as u8;
+        time /= 24;
+        let day = (time % 31) as u8 + 1;
+        time /= 31;
+        let month = (time % 12) as u8 + 1;
+        time /= 12;
+        let year = time + DateTime::MIN_YEAR;
+
+        DateTime {
+            day,
+            month,
+            year,
+            hour,
+            minute,
+        }
+    }
+}
+
+impl TryFrom<DateTime> for NotamTime {
+    type Error = Error;
+
+    fn try_from(date_time: DateTime) -> Result<Self> {
+        Self::encode(date_time)
+    }
+}
+
+impl TryFrom<NotamTime> for DateTime {
+    type Error = Error;
+
+    fn try_from(time: NotamTime) -> Result<Self> {
+        time.decode()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_le, assert_matches, assert_ok, assert_ok_eq};
+
+    #[test]
+    fn decode_example() {
+        // Example: 2024-07-15 14:30
+        // Manually calculated encoded value
+        let encoded = 30 + 60 * (14 + 24 * (14 + 31 * (6 + 12 * 24)));
+        let dt = NotamTime::from_raw(encoded).decode_unchecked();
+        assert_eq!(dt.year, 2024);
+        assert_eq!(dt.month, 7);
+        assert_eq!(dt.day, 15);
+        assert_eq!(dt.hour, 14);
+        assert_eq!(dt.minute, 30);
+    }
+
+    #[test]
+    fn round_trip() {
+        for (year, month, day, hour, minute) in [
+            (2000, 1, 1, 0, 0),
+            (2024, 2, 29, 23, 59),
+            (2024, 7, 15, 14, 30),
+            (2124, 12, 31, 23, 59),
+        ] {
+            let date_time = assert_ok!(DateTime::new(year, month, day, hour, minute));
+            let encoded = assert_ok!(NotamTime::encode(date_time));
+            assert_le!(encoded.to_raw(), NotamTime::MAX_ACTIVE_TIME);
+            assert_ok_eq!(encoded.decode(), date_time);
+        }
+
+        assert_ok_eq!(
+            NotamTime::encode(assert_ok!(DateTime::new(2000, 1, 1, 0, 0))),
+            NotamTime(0)
+        );
+    }
+
+    #[test]
+    fn range_checks() {
+        let date_time = |year, month, day| DateTime {
+            day,
+            month,
+            year,
+            hour: 0,
+            minute: 0,
+        };
+
+        for invalid in [
+            date_time(1999, 12, 31),
+            date_time(2024, 2, 30),
+            date_time(2125, 1, 1),
+        ] {
+            assert_matches!(NotamTime::encode(invalid), Err(Error::InvalidDateTime(_)));
+        }
+
+        // February 31 (day offset 30 in month offset 1)
+        let february_31 = NotamTime::from_raw(60 * 24 * (30 + 31));
+        assert_eq!(february_31.decode_unchecked(), date_time(2000, 2, 31));
+        assert_matches!(february_31.decode(), Err(Error::InvalidDateTime(_)));
+
+        // Beyond `DateTime::MAX_YEAR`
+        assert_matches!(
+            NotamTime::from_raw(u32::MAX).decode(),
+            Err(Error::InvalidDateTime(_))
+        );
+    }
+}
@@ -1,13 +1,81 @@
 //! High-level CUB file reader with iterator-based API
 
-use crate::error::Result;
-use crate::raw::{Header, Item, ItemData, PointOp};
-use crate::{Airspace, BoundingBox};
+use crate::error::{Error, Result};
+use crate::raw::{Header, Item, ItemData, NotamTime, OwnedStrings, PointOp, read_item_data};
+use crate::utils::decode;
+use crate::{Airspace, BoundingBox, DateTime, Point};
 use std::borrow::Cow;
+use std::fmt;
 use std::fs::File;
-use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::io::{BufRead, BufReader, ErrorKind, Read, Seek, SeekFrom};
+use std::iter::Enumerate;
 use std::path::Path;
 
+/// How the reader handles airspaces with corrupt item data
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Strictness {
+    /// Yield an error for every airspace that cannot be decoded
+    #[default]
+    Strict,
+    /// Skip airspaces that cannot be decoded and record a [`ReadWarning`]
+    /// instead
+    ///
+    /// Unknown attribute flags, which are silently ignored in strict mode, are
+    /// also recorded as warnings.
+    Lenient,
+}
+
+/// Options for `CubReader::with_options()`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReadOptions {
+    pub strictness: Strictness,
+}
+
+/// Problem encountered while decoding an airspace in lenient mode
+///
+/// Collected by the reader and available via `CubReader::warnings()`.
+#[derive(Debug)]
+pub struct ReadWarning {
+    /// Index of the affected item
+    pub item_index: usize,
+    /// Absolute byte offset of the affected item data in the file
+    pub offset: u64,
+    pub cause: WarningCause,
+}
+
+/// Cause of a `ReadWarning`
+#[derive(Debug)]
+pub enum WarningCause {
+    /// The airspace could not be decoded and was skipped
+    Skipped(Error),
+    /// The attribute section ended with an unknown flag
+    ///
+    /// The airspace was returned, but any attributes after this flag were
+    /// ignored.
+    UnknownAttributeFlag(u8),
+}
+
+impl fmt::Display for ReadWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "item {} at offset {}: {}",
+            self.item_index, self.offset, self.cause
+        )
+    }
+}
+
+impl fmt::Display for WarningCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Skipped(error) => write!(f, "skipped airspace ({error})"),
+            Self::UnknownAttributeFlag(flag) => {
+                write!(f, "ignored attributes after unknown flag 0x{flag:02X}")
+            }
+        }
+    }
+}
+
 /// High-level CUB file reader with iterator-based API
 ///
 /// Provides convenient access to CUB file contents with automatic decoding of:
@@ -27,10 +95,36 @@ use std::path::Path;
 /// }
 /// # Ok::<(), seeyou_cub::Error>(())
 /// ```
+///
+/// # Lenient Mode
+///
+/// By default, airspaces with corrupt item data are yielded as errors. With
+/// [`Strictness::Lenient`] they are skipped instead, and the problems are
+/// collected as [`ReadWarning`]s. Since `read_airspaces()` always yields
+/// exactly one result per airspace, use `read_airspaces_lenient()` to iterate
+/// over all airspaces while skipping the corrupt ones:
+///
+/// ```no_run
+/// use seeyou_cub::{CubReader, ReadOptions, Strictness};
+/// use std::fs::File;
+///
+/// let options = ReadOptions { strictness: Strictness::Lenient };
+/// let mut reader = CubReader::with_options(File::open("airspace.cub")?, options)?;
+///
+/// let airspaces = reader.read_airspaces_lenient().collect::<Result<Vec<_>, _>>()?;
+/// for warning in reader.warnings() {
+///     eprintln!("Warning: {warning}");
+/// }
+/// # Ok::<(), seeyou_cub::Error>(())
+/// ```
 pub struct CubReader<R: Read + Seek> {
     reader: BufReader<R>,
     header: Header,
     items: Vec<Item>,
+    /// Position of the first item in `reader`
+    items_position: u64,
+    options: ReadOptions,
+    warnings: Vec<ReadWarning>,
 }
 
 impl CubReader<File> {
@@ -47,23 +141,92 @@ impl<R: Read + Seek> CubReader<R> {
     /// Create a reader from any `Read + Seek` source
     ///
     /// Reads the header immediately to validate the format and store metadata.
+    ///
+    /// Errors returned by the reader carry the item index and byte offset of
+    /// the failing structure, see `Error::WithContext`.
     pub fn new(reader: R) -> Result<Self> {
+        Self::with_options(reader, ReadOptions::default())
+    }
+
+    /// Create a reader from any `Read + Seek` source with custom options
+    ///
+    /// The header and items are always read strictly; the options only affect
+    /// how item data is decoded.
+    pub fn with_options(reader: R, options: ReadOptions) -> Result<Self> {
         let mut reader = BufReader::new(reader);
 
-        let header = Header::read(&mut reader)?;
+        let header = Header::read(&mut reader)
+            .map_err(|error| with_stream_position(error, &mut reader, None))?;
+        let items_position = reader.stream_position()?;
 
         let mut items = Vec::with_capacity(header.hdr_items as usize);
-        for _ in 0..header.hdr_items {
-            items.push(Item::read(&mut reader, &header)?);
+        for index in 0..header.hdr_items as usize {
+            let item = Item::read(&mut reader, &header)
+                .map_err(|error| with_stream_position(error, &mut reader, Some(index)))?;
+            items.push(item);
         }
 
         Ok(Self {
             reader,
             header,
             items,
+            items_position,
+            options,
+            warnings: Vec::new(),
         })
     }
 
+    /// Get the warnings collected so far in lenient mode
+    ///
+    /// Warnings are appended as airspaces are decoded, so reading the same
+    /// airspace twice records its warnings twice.
+    pub fn warnings(&self) -> &[ReadWarning] {
+        &self.warnings
+    }
+
+    /// Remove and return the warnings collected so far
+    pub fn take_warnings(&mut self) -> Vec<ReadWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Verify the CRC32 stored in the header against the file contents
+    ///
+    /// The checksum covers everything following the header, i.e. the items
+    /// and the item data. Files written without a checksum (e.g. by
+    /// `CubWriter` without `with_crc(true)`) do not set the CRC flag in the
+    /// header, see `Header::has_crc32()`.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(true)` if the checksum matches, `Ok(false)` if the file carries no
+    /// checksum, or `Error::CrcMismatch` if the file contents are corrupt
+    pub fn verify_crc(&mut self) -> Result<bool> {
+        if !self.header.has_crc32() {
+            return Ok(false);
+        }
+        let expected = self.header.crc32;
+
+        self.reader.seek(SeekFrom::Start(self.items_position))?;
+
+        let mut hasher = crc32fast::Hasher::new();
+        loop {
+            let buffer = self.reader.fill_buf()?;
+            if buffer.is_empty() {
+                break;
+            }
+            hasher.update(buffer);
+            let len = buffer.len();
+            self.reader.consume(len);
+        }
+
+        let found = hasher.finalize();
+        if found != expected {
+            return Err(Error::CrcMismatch { expected, found });
+        }
+
+        Ok(true)
+    }
+
     /// Get reference to a parsed header
     pub fn raw_header(&self) -> &Header {
         &self.header
@@ -84,18 +247,174 @@ impl<R: Read + Seek> CubReader<R> {
         self.header.bounding_box()
     }
 
+    /// Get references to all parsed items
+    ///
+    /// Items are loaded eagerly by `CubReader::new()`, so this does not
+    /// perform any I/O.
+    pub fn raw_items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// Get the number of airspaces in the file
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check if the file contains no airspaces
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Read a single airspace by index
+    ///
+    /// Seeks directly to the item data of the requested airspace, without
+    /// decoding any of the airspaces before it.
+    ///
+    /// Returns `None` if `index` is out of bounds. Since there is nothing to
+    /// skip to, decoding errors are returned even in lenient mode.
+    pub fn airspace(&mut self, index: usize) -> Option<Result<Airspace>> {
+        let item = self.items.get(index)?;
+        let mut source = Source {
+            reader: &mut self.reader,
+            header: &self.header,
+            options: self.options,
+            warnings: &mut self.warnings,
+        };
+        Some(source.read_airspace_strict(index, item))
+    }
+
     /// Create iterator over all airspaces in the file
     ///
     /// Returns an iterator that yields `Result<Airspace>` for each airspace.
     ///
     /// The iterator performs lazy parsing - airspaces are only decoded when `.next()` is called.
+    ///
+    /// The iterator yields exactly one result per airspace, so decoding errors
+    /// are yielded even in lenient mode. Use `read_airspaces_lenient()` to
+    /// skip them instead.
     pub fn read_airspaces(&mut self) -> AirspaceIterator<'_, R> {
         AirspaceIterator {
-            reader: &mut self.reader,
-            header: &self.header,
-            items_iter: self.items.iter(),
+            source: Source {
+                reader: &mut self.reader,
+                header: &self.header,
+                options: self.options,
+                warnings: &mut self.warnings,
+            },
+            items_iter: self.items.iter().enumerate(),
+        }
+    }
+
+    /// Create iterator over all airspaces in the file that skips corrupt
+    /// airspaces
+    ///
+    /// Airspaces that cannot be decoded are skipped and recorded in
+    /// `warnings()`, regardless of the configured `Strictness`. I/O errors of
+    /// the underlying reader are still yielded.
+    pub fn read_airspaces_lenient(&mut self) -> LenientAirspaceIterator<'_, R> {
+        LenientAirspaceIterator {
+            source: Source {
+                reader: &mut self.reader,
+                header: &self.header,
+                options: ReadOptions {
+                    strictness: Strictness::Lenient,
+                },
+                warnings: &mut self.warnings,
+            },
+            items_iter: self.items.iter().enumerate(),
+        }
+    }
+
+    /// Create iterator over all airspaces intersecting a bounding box
+    ///
+    /// Only items whose pre-calculated bounding box intersects `bbox` are
+    /// decoded. All other items are skipped without reading their item data.
+    pub fn airspaces_in(
+        &mut self,
+        bbox: BoundingBox,
+    ) -> FilteredAirspaceIterator<'_, R, impl FnMut(&Item) -> bool> {
+        self.filter_airspaces(move |item| item.bounding_box.intersects(&bbox))
+    }
+
+    /// Create iterator over all airspaces within `radius` meters of a point
+    ///
+    /// Only items whose pre-calculated bounding box is within `radius` of
+    /// `point` are decoded. Note that this is a bounding box check, so the
+    /// actual airspace boundary may still be further away than `radius`.
+    pub fn airspaces_near(
+        &mut self,
+        point: Point,
+        radius: f32,
+    ) -> FilteredAirspaceIterator<'_, R, impl FnMut(&Item) -> bool> {
+        let region = BoundingBox::around(point, radius);
+        self.filter_airspaces(move |item| {
+            item.bounding_box.intersects(&region) && item.bounding_box.distance_to(point) <= radius
+        })
+    }
+
+    /// Create iterator over all airspaces that are or may be active at the
+    /// given time (UTC)
+    ///
+    /// Airspaces for which `Item::is_active_at()` returns
+    /// `Activity::Inactive` are skipped without reading their item data, while
+    /// `Activity::Maybe` airspaces are kept.
+    pub fn airspaces_active_at(
+        &mut self,
+        time: DateTime,
+    ) -> FilteredAirspaceIterator<'_, R, impl FnMut(&Item) -> bool> {
+        self.filter_airspaces(move |item| item.is_active_at(time).is_possible())
+    }
+
+    /// Create iterator over all airspaces whose `Item` matches a predicate
+    ///
+    /// The predicate is evaluated on the raw item before any item data is
+    /// read, so non-matching airspaces are never decoded.
+    pub fn filter_airspaces<P: FnMut(&Item) -> bool>(
+        &mut self,
+        predicate: P,
+    ) -> FilteredAirspaceIterator<'_, R, P> {
+        FilteredAirspaceIterator {
+            source: Source {
+                reader: &mut self.reader,
+                header: &self.header,
+                options: self.options,
+                warnings: &mut self.warnings,
+            },
+            items_iter: self.items.iter().enumerate(),
+            predicate,
         }
     }
+
+    /// Create parallel iterator over all airspaces (requires the `rayon`
+    /// feature)
+    ///
+    /// The item data of every airspace can be decoded independently, so the
+    /// file contents are read into a shared buffer once and the airspaces are
+    /// decoded on the rayon thread pool. Results are yielded in file order
+    /// when collected.
+    ///
+    /// `ReadOptions` do not apply here: decoding errors are always yielded
+    /// and no warnings are collected.
+    ///
+    /// # Returns
+    ///
+    /// The parallel iterator, or an error if the file contents cannot be read
+    #[cfg(feature = "rayon")]
+    pub fn par_airspaces(
+        &mut self,
+    ) -> Result<impl rayon::iter::IndexedParallelIterator<Item = Result<Airspace>> + '_> {
+        use rayon::prelude::*;
+
+        let mut bytes = Vec::new();
+        self.reader.seek(SeekFrom::Start(0))?;
+        self.reader.read_to_end(&mut bytes)?;
+
+        let header = &self.header;
+        Ok(self
+            .items
+            .par_iter()
+            .enumerate()
+            .map(move |(index, item)| crate::slice::decode_airspace(&bytes, header, index, item)))
+    }
 }
 
 /// Iterator over airspaces in a CUB file
@@ -103,41 +422,174 @@ impl<R: Read + Seek> CubReader<R> {
 /// Yields `Result<Airspace>` for each airspace.
 /// Created by calling `CubReader::read_airspaces()`.
 pub struct AirspaceIterator<'a, R: Read + Seek> {
-    reader: &'a mut BufReader<R>,
-    header: &'a Header,
-    items_iter: std::slice::Iter<'a, Item>,
+    source: Source<'a, R>,
+    items_iter: Enumerate<std::slice::Iter<'a, Item>>,
 }
 
-impl<R: Read + Seek> AirspaceIterator<'_, R> {
-    fn read_airspace(&mut self, item: &Item) -> Result<Airspace> {
-        let data_offset = self.header.data_offset as u64 + item.points_offset as u64;
-        self.reader.seek(SeekFrom::Start(data_offset))?;
+impl<'a, R: Read + Seek> Iterator for AirspaceIterator<'a, R> {
+    type Item = Result<Airspace>;
 
-        let raw_data = ItemData::read(self.reader, self.header)?;
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, item) = self.items_iter.next()?;
 
-        // Convert to high-level Airspace
-        convert_to_airspace(self.header, item, raw_data)
+        Some(self.source.read_airspace_strict(index, item))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items_iter.size_hint()
     }
 }
 
-impl<'a, R: Read + Seek> Iterator for AirspaceIterator<'a, R> {
+impl<'a, R: Read + Seek> ExactSizeIterator for AirspaceIterator<'a, R> {}
+
+/// Iterator over airspaces in a CUB file that skips corrupt airspaces
+///
+/// Yields `Result<Airspace>` for each airspace that can be decoded.
+/// Created by calling `CubReader::read_airspaces_lenient()`.
+pub struct LenientAirspaceIterator<'a, R: Read + Seek> {
+    source: Source<'a, R>,
+    items_iter: Enumerate<std::slice::Iter<'a, Item>>,
+}
+
+impl<'a, R: Read + Seek> Iterator for LenientAirspaceIterator<'a, R> {
     type Item = Result<Airspace>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let item = self.items_iter.next()?;
+        self.items_iter
+            .find_map(|(index, item)| self.source.read_airspace(index, item))
+    }
 
-        Some(self.read_airspace(item))
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.items_iter.size_hint().1)
+    }
+}
+
+/// Iterator over airspaces in a CUB file whose `Item` matches a predicate
+///
+/// Yields `Result<Airspace>` for each matching airspace.
+/// Created by calling `CubReader::filter_airspaces()`, `CubReader::airspaces_in()`
+/// or `CubReader::airspaces_near()`.
+pub struct FilteredAirspaceIterator<'a, R: Read + Seek, P> {
+    source: Source<'a, R>,
+    items_iter: Enumerate<std::slice::Iter<'a, Item>>,
+    predicate: P,
+}
+
+impl<'a, R: Read + Seek, P: FnMut(&Item) -> bool> Iterator for FilteredAirspaceIterator<'a, R, P> {
+    type Item = Result<Airspace>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let predicate = &mut self.predicate;
+        let source = &mut self.source;
+        self.items_iter
+            .by_ref()
+            .filter(|(_, item)| predicate(item))
+            .find_map(|(index, item)| source.read_airspace(index, item))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.items_iter.size_hint()
+        (0, self.items_iter.size_hint().1)
     }
 }
 
-impl<'a, R: Read + Seek> ExactSizeIterator for AirspaceIterator<'a, R> {}
+/// Borrowed reader state shared by the airspace iterators
+struct Source<'a, R: Read + Seek> {
+    reader: &'a mut BufReader<R>,
+    header: &'a Header,
+    options: ReadOptions,
+    warnings: &'a mut Vec<ReadWarning>,
+}
+
+impl<R: Read + Seek> Source<'_, R> {
+    /// Seek to the item data of `item` and decode it into an `Airspace`
+    ///
+    /// Returns `None` if the airspace was skipped in lenient mode.
+    fn read_airspace(&mut self, index: usize, item: &Item) -> Option<Result<Airspace>> {
+        let offset = self.header.data_offset as u64 + item.points_offset as u64;
+
+        match self.decode_airspace(offset, item) {
+            Ok((airspace, unknown_flag)) => {
+                self.warn_unknown_flag(index, offset, unknown_flag);
+                Some(Ok(airspace))
+            }
+            Err(error) if self.is_lenient() && is_corrupt_data(&error) => {
+                self.warnings.push(ReadWarning {
+                    item_index: index,
+                    offset,
+                    cause: WarningCause::Skipped(error),
+                });
+                None
+            }
+            Err(error) => Some(Err(with_stream_position(error, self.reader, Some(index)))),
+        }
+    }
+
+    /// Seek to the item data of `item` and decode it into an `Airspace`
+    /// without skipping it in lenient mode
+    fn read_airspace_strict(&mut self, index: usize, item: &Item) -> Result<Airspace> {
+        let offset = self.header.data_offset as u64 + item.points_offset as u64;
+
+        let (airspace, unknown_flag) = self
+            .decode_airspace(offset, item)
+            .map_err(|error| with_stream_position(error, self.reader, Some(index)))?;
+        self.warn_unknown_flag(index, offset, unknown_flag);
+        Ok(airspace)
+    }
+
+    fn is_lenient(&self) -> bool {
+        self.options.strictness == Strictness::Lenient
+    }
+
+    /// Record a warning for an unknown attribute flag in lenient mode
+    fn warn_unknown_flag(&mut self, index: usize, offset: u64, unknown_flag: Option<u8>) {
+        if let Some(flag) = unknown_flag.filter(|_| self.is_lenient()) {
+            self.warnings.push(ReadWarning {
+                item_index: index,
+                offset,
+                cause: WarningCause::UnknownAttributeFlag(flag),
+            });
+        }
+    }
+
+    fn decode_airspace(&mut self, offset: u64, item: &Item) -> Result<(Airspace, Option<u8>)> {
+        self.reader.seek(SeekFrom::Start(offset))?;
+
+        let (raw_data, unknown_flag) = read_item_data(&mut OwnedStrings(self.reader), self.header)?;
+
+        // Convert to high-level Airspace
+        let airspace = convert_to_airspace(self.header, item, raw_data)?;
+        Ok((airspace, unknown_flag))
+    }
+}
+
+/// Attach the item index and the current stream position to an error
+///
+/// The stream position is the offset at which decoding stopped, i.e. just
+/// after the last byte that was read.
+fn with_stream_position<R: Seek>(error: Error, reader: &mut R, item_index: Option<usize>) -> Error {
+    match reader.stream_position() {
+        Ok(position) => error.with_context(item_index, position),
+        Err(_) => error,
+    }
+}
+
+/// Check if an error was caused by corrupt item data rather than by the
+/// underlying reader
+///
+/// Truncated item data surfaces as an `UnexpectedEof` I/O error.
+fn is_corrupt_data(error: &Error) -> bool {
+    match error {
+        Error::IoError(error) => error.kind() == ErrorKind::UnexpectedEof,
+        _ => true,
+    }
+}
 
 /// Convert raw item + item data to high-level Airspace
-fn convert_to_airspace(header: &Header, item: &Item, item_data: ItemData) -> Result<Airspace> {
+pub(crate) fn convert_to_airspace<S: AsRef<[u8]>>(
+    header: &Header,
+    item: &Item,
+    item_data: ItemData<S>,
+) -> Result<Airspace> {
     // Convert coordinates from raw i16 offsets to f32 lat/lon radians
     let points = PointOp::resolve(
         &item_data.point_ops,
@@ -150,28 +602,28 @@ fn convert_to_airspace(header: &Header, item: &Item, item_data: ItemData) -> Res
     let name = item_data
         .name
         .as_ref()
-        .map(|bs| bs.decode().into_owned())
+        .map(|bs| decode(bs.as_ref()).into_owned())
         .unwrap_or_default();
     let frequency_name = item_data
         .frequency_name
         .as_ref()
-        .map(|bs| bs.decode().into_owned());
+        .map(|bs| decode(bs.as_ref()).into_owned());
     let icao_code = item_data
         .icao_code
         .as_ref()
-        .map(|bs| bs.decode().into_owned());
+        .map(|bs| decode(bs.as_ref()).into_owned());
     let exception_rules = item_data
         .exception_rules
         .as_ref()
-        .map(|bs| bs.decode().into_owned());
+        .map(|bs| decode(bs.as_ref()).into_owned());
     let notam_remarks = item_data
         .notam_remarks
         .as_ref()
-        .map(|bs| bs.decode().into_owned());
+        .map(|bs| decode(bs.as_ref()).into_owned());
     let notam_id = item_data
         .notam_id
         .as_ref()
-        .map(|bs| bs.decode().into_owned());
+        .map(|bs| decode(bs.as_ref()).into_owned());
 
     Ok(Airspace {
         // Bounding box (always populated by reader)
@@ -210,13 +662,19 @@ fn convert_to_airspace(header: &Header, item: &Item, item_data: ItemData) -> Res
         // Numeric attributes
         frequency: item_data.frequency.map(|f| f as f32 / 1000.),
         secondary_frequency: item_data.secondary_frequency.map(|f| f as f32 / 1000.),
-        notam_insert_time: item_data.notam_insert_time,
+        notam_insert_time: item_data
+            .notam_insert_time
+            .map(|time| NotamTime::from_raw(time).decode_unchecked()),
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::raw::HEADER_SIZE;
+    use claims::{
+        assert_err, assert_gt, assert_le, assert_lt, assert_matches, assert_none, assert_ok,
+    };
     use insta::assert_debug_snapshot;
 
     #[test]
@@ -264,6 +722,140 @@ mod tests {
         assert_eq!(iter.size_hint(), (1367, Some(1367)));
     }
 
+    #[test]
+    fn random_access_by_index() {
+        let mut reader = CubReader::from_path("tests/fixtures/france_2024.07.02.cub")
+            .expect("Failed to open fixture");
+
+        assert_eq!(reader.len(), 1368);
+        assert!(!reader.is_empty());
+        assert_eq!(reader.raw_items().len(), 1368);
+
+        let sequential: Vec<_> = reader
+            .read_airspaces()
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+
+        // Access out of order to verify seeking works independently of iteration
+        for index in [1367, 0, 684, 1] {
+            let airspace = reader
+                .airspace(index)
+                .expect("Expected airspace at index")
+                .expect("Failed to read airspace");
+
+            assert_eq!(airspace.name, sequential[index].name);
+            assert_eq!(airspace.points, sequential[index].points);
+        }
+
+        assert_none!(reader.airspace(1368));
+    }
+
+    #[test]
+    fn airspaces_in_bounding_box() {
+        let mut reader = CubReader::from_path("tests/fixtures/france_2024.07.02.cub")
+            .expect("Failed to open fixture");
+
+        // Roughly the Paris region
+        let bbox = BoundingBox {
+            left: 1.5_f32.to_radians(),
+            top: 49.2_f32.to_radians(),
+            right: 3.2_f32.to_radians(),
+            bottom: 48.5_f32.to_radians(),
+        };
+
+        let airspaces: Vec<_> = reader
+            .airspaces_in(bbox)
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+
+        assert_gt!(airspaces.len(), 0);
+        assert_lt!(airspaces.len(), reader.len());
+
+        for airspace in &airspaces {
+            assert!(airspace.bounding_box.unwrap().intersects(&bbox));
+        }
+
+        let expected = reader
+            .raw_items()
+            .iter()
+            .filter(|item| item.bounding_box.intersects(&bbox))
+            .count();
+        assert_eq!(airspaces.len(), expected);
+    }
+
+    #[test]
+    fn airspaces_near_point() {
+        let mut reader = CubReader::from_path("tests/fixtures/france_2024.07.02.cub")
+            .expect("Failed to open fixture");
+
+        // Paris: 48.8566°N, 2.3522°E
+        let paris = Point::lat_lon(48.8566_f32.to_radians(), 2.3522_f32.to_radians());
+
+        let near: Vec<_> = reader
+            .airspaces_near(paris, 10_000.)
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+
+        let wider: Vec<_> = reader
+            .airspaces_near(paris, 50_000.)
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+
+        assert_gt!(near.len(), 0);
+        assert_lt!(near.len(), wider.len());
+
+        for airspace in &near {
+            assert_le!(airspace.bounding_box.unwrap().distance_to(paris), 10_000.);
+        }
+    }
+
+    #[test]
+    fn airspaces_active_at_time() {
+        let airspace = |name: &str, days: u16, end_date: Option<DateTime>| Airspace {
+            name: name.to_string(),
+            days_active: crate::DaysActive::from_bits(days),
+            end_date,
+            points: vec![Point::lat_lon(0.8, 0.1), Point::lat_lon(0.81, 0.11)],
+            ..Default::default()
+        };
+
+        let expired = DateTime {
+            day: 1,
+            month: 1,
+            year: 2024,
+            hour: 0,
+            minute: 0,
+        };
+
+        let mut cursor = std::io::Cursor::new(Vec::new());
+        crate::CubWriter::new("Activity")
+            .add_airspace(airspace("always", 0, None))
+            .add_airspace(airspace("saturday", 0x040, None))
+            .add_airspace(airspace("saturday or AUP", 0x140, None))
+            .add_airspace(airspace("expired", 0, Some(expired)))
+            .write(&mut cursor)
+            .expect("Failed to write");
+
+        cursor.set_position(0);
+        let mut reader = CubReader::new(cursor).expect("Failed to read");
+
+        // 2024-07-02 is a Tuesday
+        let tuesday = DateTime {
+            day: 2,
+            month: 7,
+            year: 2024,
+            hour: 12,
+            minute: 0,
+        };
+
+        let names: Vec<_> = reader
+            .airspaces_active_at(tuesday)
+            .map(|result| result.map(|airspace| airspace.name))
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+        assert_eq!(names, ["always", "saturday or AUP"]);
+    }
+
     #[test]
     fn verify_string_decoding() {
         let mut reader = CubReader::from_path("tests/fixtures/france_2024.07.02.cub")
@@ -278,4 +870,242 @@ mod tests {
         names.sort();
         assert_debug_snapshot!(names);
     }
+
+    const LENIENT: ReadOptions = ReadOptions {
+        strictness: Strictness::Lenient,
+    };
+
+    /// Read the France fixture and apply `corrupt` to the raw bytes
+    fn corrupted_fixture(corrupt: impl FnOnce(&mut Vec<u8>, &[Item], &Header)) -> Vec<u8> {
+        let mut bytes =
+            std::fs::read("tests/fixtures/france_2024.07.02.cub").expect("Failed to read fixture");
+
+        let reader = CubReader::new(std::io::Cursor::new(&bytes)).expect("Failed to open");
+        let items = reader.raw_items().to_vec();
+        let header = reader.raw_header().clone();
+
+        corrupt(&mut bytes, &items, &header);
+        bytes
+    }
+
+    #[test]
+    fn lenient_mode_without_corruption() {
+        let file = File::open("tests/fixtures/france_2024.07.02.cub").expect("Failed to open");
+        let mut reader = CubReader::with_options(file, LENIENT).expect("Failed to open fixture");
+
+        let count = reader
+            .read_airspaces_lenient()
+            .collect::<Result<Vec<_>>>()
+            .expect("Failed to read airspaces")
+            .len();
+        assert_eq!(count, 1368);
+        assert!(reader.warnings().is_empty());
+    }
+
+    #[test]
+    fn lenient_mode_skips_corrupt_airspace() {
+        let mut offset = 0;
+        let bytes = corrupted_fixture(|bytes, items, header| {
+            // Corrupt the second point operation, so that the end of the
+            // previous item is still detected correctly
+            offset = (header.data_offset + items[5].points_offset) as u64;
+            bytes[offset as usize + 5] = 0x02;
+        });
+
+        // Strict mode yields an error for the corrupt airspace
+        let mut reader = CubReader::new(std::io::Cursor::new(&bytes)).expect("Failed to open");
+        let results: Vec<_> = reader.read_airspaces().collect();
+        assert_eq!(results.len(), 1368);
+        let error = assert_err!(&results[5]);
+        assert_matches!(error.inner(), Error::UnexpectedPointFlag(0x02));
+        assert_eq!(error.item_index(), Some(5));
+        // Decoding stops right after the corrupt point flag
+        let failed_at = offset + 6;
+        assert_eq!(error.offset(), Some(failed_at));
+        assert_eq!(
+            error.to_string(),
+            format!("Unexpected point flag: 0x02 (at offset {failed_at}, item 5)")
+        );
+        assert!(reader.warnings().is_empty());
+
+        // Lenient mode still yields one result per airspace from
+        // `read_airspaces()`
+        let cursor = std::io::Cursor::new(&bytes);
+        let mut reader = CubReader::with_options(cursor, LENIENT).expect("Failed to open");
+        let mut iter = reader.read_airspaces();
+        assert_eq!(iter.len(), 1368);
+        assert_matches!(iter.nth(5), Some(Err(_)));
+        assert!(reader.warnings().is_empty());
+
+        // `read_airspaces_lenient()` skips it and records a warning
+        let airspaces: Vec<_> = reader
+            .read_airspaces_lenient()
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+        assert_eq!(airspaces.len(), 1367);
+
+        let warnings = reader.take_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].item_index, 5);
+        assert_eq!(warnings[0].offset, offset);
+        assert_matches!(
+            &warnings[0].cause,
+            WarningCause::Skipped(Error::UnexpectedPointFlag(0x02))
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            format!("item 5 at offset {offset}: skipped airspace (Unexpected point flag: 0x02)")
+        );
+        assert!(reader.warnings().is_empty());
+    }
+
+    #[test]
+    fn lenient_mode_skips_truncated_airspace() {
+        let bytes = corrupted_fixture(|bytes, items, header| {
+            // Cut the item data of the last airspace in the middle of its name
+            let last = items.iter().map(|item| item.points_offset).max().unwrap();
+            let offset = (header.data_offset + last) as usize;
+            let name_flag = (offset..bytes.len())
+                .step_by(5)
+                .find(|&i| bytes[i] & 0x40 != 0)
+                .unwrap();
+            bytes.truncate(name_flag + 7);
+        });
+
+        let cursor = std::io::Cursor::new(&bytes);
+        let mut reader = CubReader::with_options(cursor, LENIENT).expect("Failed to open");
+        assert_eq!(reader.read_airspaces_lenient().size_hint(), (0, Some(1368)));
+
+        let count = reader
+            .read_airspaces_lenient()
+            .collect::<Result<Vec<_>>>()
+            .expect("Failed to read airspaces")
+            .len();
+        assert_eq!(count, 1367);
+
+        assert_eq!(reader.warnings().len(), 1);
+        let warning = &reader.warnings()[0];
+        assert_matches!(
+            &warning.cause,
+            WarningCause::Skipped(Error::IoError(error)) if error.kind() == ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn lenient_mode_reports_unknown_attribute_flag() {
+        let bytes = corrupted_fixture(|bytes, _, _| bytes.push(0x22));
+
+        let cursor = std::io::Cursor::new(&bytes);
+        let mut reader = CubReader::with_options(cursor, LENIENT).expect("Failed to open");
+        let count = reader
+            .read_airspaces()
+            .collect::<Result<Vec<_>>>()
+            .expect("Failed to read airspaces")
+            .len();
+        assert_eq!(count, 1368);
+
+        assert_eq!(reader.warnings().len(), 1);
+        let warning = &reader.warnings()[0];
+        assert_matches!(&warning.cause, WarningCause::UnknownAttributeFlag(0x22));
+
+        // Strict mode silently ignores unknown attribute flags
+        let mut reader = CubReader::new(std::io::Cursor::new(&bytes)).expect("Failed to open");
+        let count = reader
+            .read_airspaces()
+            .collect::<Result<Vec<_>>>()
+            .expect("Failed to read airspaces")
+            .len();
+        assert_eq!(count, 1368);
+        assert!(reader.warnings().is_empty());
+    }
+
+    #[test]
+    fn invalid_notam_insert_time() {
+        let reader = CubReader::from_path("tests/fixtures/france_2024.07.02.cub")
+            .expect("Failed to open fixture");
+        let item_data = |notam_insert_time| ItemData::<&[u8]> {
+            point_ops: vec![],
+            name: None,
+            frequency: None,
+            frequency_name: None,
+            icao_code: None,
+            secondary_frequency: None,
+            exception_rules: None,
+            notam_remarks: None,
+            notam_id: None,
+            notam_insert_time: Some(notam_insert_time),
+        };
+        let convert = |notam_insert_time| {
+            let item = &reader.raw_items()[0];
+            convert_to_airspace(reader.raw_header(), item, item_data(notam_insert_time))
+        };
+
+        let airspace = convert(0).expect("Failed to convert airspace");
+        assert_eq!(
+            airspace.notam_insert_time,
+            Some(DateTime::new(2000, 1, 1, 0, 0).unwrap())
+        );
+
+        // Out-of-range values are decoded like `Item::start_date()`
+        let february_31 = 60 * 24 * (30 + 31);
+        let airspace = assert_ok!(convert(february_31));
+        assert_eq!(
+            airspace.notam_insert_time,
+            Some(NotamTime::from_raw(february_31).decode_unchecked())
+        );
+    }
+
+    #[test]
+    fn header_errors_carry_offset() {
+        let mut bytes =
+            std::fs::read("tests/fixtures/france_2024.07.02.cub").expect("Failed to read fixture");
+        bytes[0] = 0;
+
+        let result = CubReader::new(std::io::Cursor::new(&bytes));
+        let error = assert_err!(result.map(|_| ()));
+        assert_matches!(error.inner(), Error::InvalidMagicBytes);
+        assert_eq!(error.item_index(), None);
+        // Decoding stops right after the magic bytes
+        assert_eq!(error.offset(), Some(4));
+    }
+
+    #[test]
+    fn item_errors_carry_index_and_offset() {
+        let mut bytes =
+            std::fs::read("tests/fixtures/france_2024.07.02.cub").expect("Failed to read fixture");
+
+        // Cut the file in the middle of the third item
+        let header = Header::read(&mut &bytes[..]).expect("Failed to read header");
+        let size_of_item = header.size_of_item as usize;
+        bytes.truncate(HEADER_SIZE + 2 * size_of_item + 10);
+
+        let result = CubReader::new(std::io::Cursor::new(&bytes));
+        let error = assert_err!(result.map(|_| ()));
+        assert_matches!(error.inner(), Error::IoError(_));
+        assert_eq!(error.item_index(), Some(2));
+        assert_eq!(error.offset(), Some(bytes.len() as u64));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_airspaces_matches_sequential() {
+        use rayon::prelude::*;
+
+        let mut reader = CubReader::from_path("tests/fixtures/france_2024.07.02.cub")
+            .expect("Failed to open fixture");
+
+        let sequential: Vec<_> = reader
+            .read_airspaces()
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+
+        let parallel: Vec<_> = reader
+            .par_airspaces()
+            .expect("Failed to read file")
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+
+        // `Airspace` does not implement `PartialEq`
+        assert_eq!(format!("{parallel:?}"), format!("{sequential:?}"));
+    }
 }
@@ -0,0 +1,284 @@
+//! Zero-copy CUB file reader over a byte slice
+
+use crate::error::{Error, Result};
+use crate::raw::{BorrowedStrings, HEADER_SIZE, Header, Item, ItemData, read_item_data};
+use crate::reader::convert_to_airspace;
+use crate::{Airspace, BoundingBox};
+use std::borrow::Cow;
+use std::io::ErrorKind;
+
+/// CUB file reader that works directly on the file contents in memory
+///
+/// Unlike `CubReader`, this reader does not perform any I/O and does not keep
+/// any mutable state. Only the header is parsed up front, items and item data
+/// are parsed on demand, and the strings of `raw_item_data()` borrow from the
+/// underlying slice instead of being copied.
+///
+/// This makes it cheap to create a new `CubSlice` for every request over the
+/// same memory-mapped file (e.g. via the `memmap2` crate), and a single
+/// `CubSlice` can be shared between threads.
+///
+/// # Example
+///
+/// ```no_run
+/// use seeyou_cub::CubSlice;
+///
+/// let bytes = std::fs::read("airspace.cub")?;
+/// let cub = CubSlice::new(&bytes)?;
+///
+/// for index in 0..cub.len() {
+///     let item = cub.raw_item(index).unwrap()?;
+///     let item_data = cub.raw_item_data(&item)?;
+///     if let Some(name) = item_data.name {
+///         println!("{}", seeyou_cub::utils::decode(name));
+///     }
+/// }
+/// # Ok::<(), seeyou_cub::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct CubSlice<'a> {
+    bytes: &'a [u8],
+    header: Header,
+}
+
+impl<'a> CubSlice<'a> {
+    /// Create a reader over the contents of a CUB file
+    ///
+    /// Parses the header immediately to validate the format.
+    pub fn new(bytes: &'a [u8]) -> Result<Self> {
+        let mut remaining = bytes;
+        let header = Header::read(&mut remaining)
+            .map_err(|error| error.with_context(None, position(bytes, remaining)))?;
+        Ok(Self { bytes, header })
+    }
+
+    /// Get reference to a parsed header
+    pub fn raw_header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Get the CUB file title
+    pub fn title(&self) -> Cow<'_, str> {
+        self.header.title.decode()
+    }
+
+    /// Get bounding box covering all airspaces
+    ///
+    /// This value is read from the file header.
+    pub fn bounding_box(&self) -> &BoundingBox {
+        self.header.bounding_box()
+    }
+
+    /// Get the number of airspaces in the file
+    pub fn len(&self) -> usize {
+        self.header.hdr_items.max(0) as usize
+    }
+
+    /// Check if the file contains no airspaces
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Parse a single item by index
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    pub fn raw_item(&self, index: usize) -> Option<Result<Item>> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let offset = HEADER_SIZE + index * self.header.size_of_item as usize;
+        let mut remaining = self.bytes.get(offset..).unwrap_or_default();
+        let result = Item::read(&mut remaining, &self.header);
+
+        Some(
+            result
+                .map_err(|error| error.with_context(Some(index), position(self.bytes, remaining))),
+        )
+    }
+
+    /// Parse the item data of `item` with strings borrowed from the file contents
+    ///
+    /// Like the functions in `raw`, errors do not carry location context.
+    pub fn raw_item_data(&self, item: &Item) -> Result<ItemData<&'a [u8]>> {
+        read_borrowed_item_data(self.bytes, &self.header, item)
+    }
+
+    /// Read a single airspace by index
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    pub fn airspace(&self, index: usize) -> Option<Result<Airspace>> {
+        let item = match self.raw_item(index)? {
+            Ok(item) => item,
+            Err(error) => return Some(Err(error)),
+        };
+
+        Some(decode_airspace(self.bytes, &self.header, index, &item))
+    }
+
+    /// Create iterator over all airspaces in the file
+    pub fn airspaces(&self) -> impl ExactSizeIterator<Item = Result<Airspace>> + '_ {
+        (0..self.len()).map(|index| self.airspace(index).expect("index is in bounds"))
+    }
+
+    /// Create parallel iterator over all airspaces in the file (requires the
+    /// `rayon` feature)
+    ///
+    /// Airspaces are decoded on the rayon thread pool and yielded in file
+    /// order when collected.
+    #[cfg(feature = "rayon")]
+    pub fn par_airspaces(
+        &self,
+    ) -> impl rayon::iter::IndexedParallelIterator<Item = Result<Airspace>> + '_ {
+        use rayon::prelude::*;
+
+        (0..self.len())
+            .into_par_iter()
+            .map(|index| self.airspace(index).expect("index is in bounds"))
+    }
+}
+
+fn item_data_offset(header: &Header, item: &Item) -> u64 {
+    header.data_offset as u64 + item.points_offset as u64
+}
+
+/// Parse item data with borrowed strings from the file contents
+fn read_borrowed_item_data<'a>(
+    bytes: &'a [u8],
+    header: &Header,
+    item: &Item,
+) -> Result<ItemData<&'a [u8]>> {
+    let bytes = usize::try_from(item_data_offset(header, item))
+        .ok()
+        .and_then(|offset| bytes.get(offset..))
+        .ok_or_else(|| Error::IoError(ErrorKind::UnexpectedEof.into()))?;
+
+    let (item_data, _) = read_item_data(&mut BorrowedStrings(bytes), header)?;
+    Ok(item_data)
+}
+
+/// Decode the airspace of an already parsed item from the file contents
+///
+/// Errors carry the item index and the offset at which decoding failed as
+/// context.
+pub(crate) fn decode_airspace(
+    bytes: &[u8],
+    header: &Header,
+    index: usize,
+    item: &Item,
+) -> Result<Airspace> {
+    let Some(remaining) = usize::try_from(item_data_offset(header, item))
+        .ok()
+        .and_then(|offset| bytes.get(offset..))
+    else {
+        let error = Error::IoError(ErrorKind::UnexpectedEof.into());
+        return Err(error.with_context(Some(index), bytes.len() as u64));
+    };
+
+    let mut reader = BorrowedStrings(remaining);
+
+    read_item_data(&mut reader, header)
+        .and_then(|(item_data, _)| convert_to_airspace(header, item, item_data))
+        .map_err(|error| error.with_context(Some(index), position(bytes, reader.0)))
+}
+
+/// Get the offset of `remaining`, a tail of `bytes`, relative to the start
+/// of `bytes`
+pub(crate) fn position(bytes: &[u8], remaining: &[u8]) -> u64 {
+    (bytes.len() - remaining.len()) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CubReader;
+    use claims::{assert_err, assert_matches, assert_none, assert_some};
+
+    const FIXTURE: &str = "tests/fixtures/france_2024.07.02.cub";
+
+    #[test]
+    fn matches_cub_reader() {
+        let bytes = std::fs::read(FIXTURE).expect("Failed to read fixture");
+        let cub = CubSlice::new(&bytes).expect("Failed to parse header");
+
+        let mut reader = CubReader::from_path(FIXTURE).expect("Failed to open fixture");
+        assert_eq!(cub.raw_header(), reader.raw_header());
+        assert_eq!(cub.title(), reader.title());
+        assert_eq!(cub.len(), reader.len());
+
+        let expected: Vec<_> = reader
+            .read_airspaces()
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+        let actual: Vec<_> = cub
+            .airspaces()
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+        // `Airspace` does not implement `PartialEq`
+        assert_eq!(format!("{actual:?}"), format!("{expected:?}"));
+
+        assert_none!(cub.raw_item(cub.len()));
+        assert_none!(cub.airspace(cub.len()));
+    }
+
+    #[test]
+    fn item_data_borrows_strings() {
+        let bytes = std::fs::read(FIXTURE).expect("Failed to read fixture");
+        let cub = CubSlice::new(&bytes).expect("Failed to parse header");
+
+        let item = cub.raw_item(0).unwrap().expect("Failed to parse item");
+        let item_data = cub.raw_item_data(&item).expect("Failed to parse item data");
+
+        let name = assert_some!(item_data.name);
+        assert_eq!(name, b"R265 LA GREMUSE");
+        assert!(bytes.as_ptr_range().contains(&name.as_ptr()));
+    }
+
+    #[test]
+    fn truncated_file() {
+        let bytes = std::fs::read(FIXTURE).expect("Failed to read fixture");
+        let cub = CubSlice::new(&bytes).expect("Failed to parse header");
+        let item = cub.raw_item(0).unwrap().expect("Failed to parse item");
+        let data_offset = item_data_offset(cub.raw_header(), &item) as usize;
+
+        // Cut the file in the middle of the first item data
+        let truncated = &bytes[..data_offset + 7];
+        let cub = CubSlice::new(truncated).expect("Failed to parse header");
+
+        let error = assert_err!(cub.airspace(0).unwrap());
+        assert_eq!(error.item_index(), Some(0));
+        assert_eq!(error.offset(), Some(truncated.len() as u64));
+
+        // Cut the file before the first item data
+        let truncated = &bytes[..data_offset - 1];
+        let cub = CubSlice::new(truncated).expect("Failed to parse header");
+
+        let error = assert_err!(cub.airspace(0).unwrap());
+        assert_matches!(error.inner(), Error::IoError(_));
+        assert_eq!(error.offset(), Some(truncated.len() as u64));
+
+        // Cut the file in the middle of the items
+        let truncated = &bytes[..HEADER_SIZE + 10];
+        let cub = CubSlice::new(truncated).expect("Failed to parse header");
+        let error = assert_err!(cub.raw_item(0).unwrap());
+        assert_eq!(error.item_index(), Some(0));
+        assert_eq!(error.offset(), Some(truncated.len() as u64));
+        assert_err!(cub.raw_item(cub.len() - 1).unwrap());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_airspaces_matches_sequential() {
+        use rayon::prelude::*;
+
+        let bytes = std::fs::read(FIXTURE).expect("Failed to read fixture");
+        let cub = CubSlice::new(&bytes).expect("Failed to parse header");
+
+        let sequential: Vec<_> = cub.airspaces().map(|result| result.unwrap().name).collect();
+        let parallel: Vec<_> = cub
+            .par_airspaces()
+            .map(|result| result.unwrap().name)
+            .collect();
+        assert_eq!(parallel, sequential);
+    }
+}
@@ -0,0 +1,238 @@
+//! Terrain elevation lookup for resolving AGL airspace limits
+//!
+//! Airspace limits with `AltStyle::AboveGroundLevel` are relative to the
+//! ground below the aircraft. A [`TerrainProvider`] supplies that ground
+//! elevation, so that such limits can be compared against GPS altitudes via
+//! `Airspace::lower_limit_msl_at()`, `Airspace::upper_limit_msl_at()` and
+//! `Airspace::contains_3d_with_terrain()`.
+//!
+//! [`HgtTerrain`] reads SRTM `.hgt` tiles from a local directory. Closures of
+//! type `Fn(Point) -> Option<f32>` can be used as providers as well:
+//!
+//! ```
+//! use seeyou_cub::Point;
+//! use seeyou_cub::terrain::TerrainProvider;
+//!
+//! let flat = |_: Point| Some(450.);
+//! assert_eq!(flat.elevation_at(Point::lat_lon(0.8, 0.1)), Some(450.));
+//! ```
+
+use crate::Point;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Source of ground elevation data
+pub trait TerrainProvider {
+    /// Get the ground elevation at `point` in meters above mean sea level
+    ///
+    /// Returns `None` if no elevation data is available for the point.
+    fn elevation_at(&self, point: Point) -> Option<f32>;
+}
+
+impl<F: Fn(Point) -> Option<f32>> TerrainProvider for F {
+    fn elevation_at(&self, point: Point) -> Option<f32> {
+        self(point)
+    }
+}
+
+/// Value of missing data points in SRTM tiles
+const HGT_VOID: i16 = -32768;
+
+/// Terrain provider reading SRTM `.hgt` tiles from a directory
+///
+/// Each tile covers one degree of latitude and longitude and is named after
+/// its south-west corner (e.g. `N45E006.hgt` covers 45°N-46°N and 6°E-7°E).
+/// The tiles contain a square grid of big-endian `i16` elevations in meters,
+/// ordered from north to south and west to east. Both SRTM1 (3601×3601) and
+/// SRTM3 (1201×1201) tiles are supported.
+///
+/// Tiles are loaded on first use and kept in memory. Elevations are
+/// interpolated bilinearly between the four surrounding samples. Points on
+/// missing tiles or next to void samples have no elevation.
+///
+/// ```no_run
+/// use seeyou_cub::Point;
+/// use seeyou_cub::terrain::{HgtTerrain, TerrainProvider};
+///
+/// let terrain = HgtTerrain::new("srtm");
+/// let point = Point::lat_lon(45.9_f32.to_radians(), 6.9_f32.to_radians());
+/// println!("{:?}", terrain.elevation_at(point));
+/// ```
+#[derive(Debug)]
+pub struct HgtTerrain {
+    directory: PathBuf,
+    tiles: Mutex<TileCache>,
+}
+
+/// Loaded tiles by south-west corner, `None` for missing or invalid tiles
+type TileCache = HashMap<(i32, i32), Option<Arc<HgtTile>>>;
+
+impl HgtTerrain {
+    /// Create a provider for the `.hgt` tiles in `directory`
+    ///
+    /// No files are read until elevations are requested.
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            tiles: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get the tile with the given south-west corner, loading it if needed
+    fn tile(&self, lat: i32, lon: i32) -> Option<Arc<HgtTile>> {
+        let mut tiles = self.tiles.lock().unwrap_or_else(|error| error.into_inner());
+        tiles
+            .entry((lat, lon))
+            .or_insert_with(|| {
+                let path = self.directory.join(hgt_file_name(lat, lon));
+                let bytes = std::fs::read(path).ok()?;
+                HgtTile::from_bytes(&bytes).map(Arc::new)
+            })
+            .clone()
+    }
+}
+
+impl TerrainProvider for HgtTerrain {
+    fn elevation_at(&self, point: Point) -> Option<f32> {
+        let lat = point.lat.to_degrees();
+        let lon = point.lon.to_degrees();
+
+        let (lat_floor, lon_floor) = (lat.floor(), lon.floor());
+        let tile = self.tile(lat_floor as i32, lon_floor as i32)?;
+
+        // Row 0 is the northern edge of the tile
+        let max = (tile.size - 1) as f32;
+        let row = (lat_floor + 1. - lat) * max;
+        let col = (lon - lon_floor) * max;
+        tile.interpolate(row, col)
+    }
+}
+
+/// A single square grid of elevation samples
+#[derive(Debug)]
+struct HgtTile {
+    size: usize,
+    samples: Vec<i16>,
+}
+
+impl HgtTile {
+    /// Parse a tile from the raw file contents
+    ///
+    /// Returns `None` if the contents are not a square grid of samples.
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let count = bytes.len() / 2;
+        let size = (count as f64).sqrt() as usize;
+        if !bytes.len().is_multiple_of(2) || size < 2 || size * size != count {
+            return None;
+        }
+
+        let samples = bytes
+            .chunks_exact(2)
+            .map(|chunk| i16::from_be_bytes([chunk[0], chunk[1]]))
+            .collect();
+
+        Some(Self { size, samples })
+    }
+
+    fn sample(&self, row: usize, col: usize) -> Option<f32> {
+        let value = self.samples[row * self.size + col];
+        (value != HGT_VOID).then_some(value as f32)
+    }
+
+    /// Bilinear interpolation at fractional grid coordinates
+    ///
+    /// Void samples only cause a `None` result if they have a non-zero weight.
+    fn interpolate(&self, row: f32, col: f32) -> Option<f32> {
+        let max = self.size - 1;
+        let row0 = (row.floor().max(0.) as usize).min(max - 1);
+        let col0 = (col.floor().max(0.) as usize).min(max - 1);
+        let (dr, dc) = (row - row0 as f32, col - col0 as f32);
+
+        let corners = [
+            (row0, col0, (1. - dr) * (1. - dc)),
+            (row0, col0 + 1, (1. - dr) * dc),
+            (row0 + 1, col0, dr * (1. - dc)),
+            (row0 + 1, col0 + 1, dr * dc),
+        ];
+
+        let mut elevation = 0.;
+        for (row, col, weight) in corners {
+            if weight > 0. {
+                elevation += self.sample(row, col)? * weight;
+            }
+        }
+        Some(elevation)
+    }
+}
+
+/// File name of the tile with the given south-west corner (e.g. `N45E006.hgt`)
+fn hgt_file_name(lat: i32, lon: i32) -> String {
+    let ns = if lat < 0 { 'S' } else { 'N' };
+    let ew = if lon < 0 { 'W' } else { 'E' };
+    format!("{ns}{:02}{ew}{:03}.hgt", lat.abs(), lon.abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_lt, assert_none, assert_some};
+    use std::path::Path;
+
+    fn point(lat: f32, lon: f32) -> Point {
+        Point::lat_lon(lat.to_radians(), lon.to_radians())
+    }
+
+    /// Write a 3×3 tile with the given samples (north to south)
+    fn write_tile(directory: &Path, name: &str, samples: [i16; 9]) {
+        let bytes: Vec<u8> = samples.iter().flat_map(|s| s.to_be_bytes()).collect();
+        std::fs::create_dir_all(directory).unwrap();
+        std::fs::write(directory.join(name), bytes).unwrap();
+    }
+
+    #[test]
+    fn file_names() {
+        assert_eq!(hgt_file_name(45, 6), "N45E006.hgt");
+        assert_eq!(hgt_file_name(-1, -78), "S01W078.hgt");
+        assert_eq!(hgt_file_name(0, 0), "N00E000.hgt");
+    }
+
+    #[test]
+    fn hgt_elevations() {
+        let directory =
+            std::env::temp_dir().join(format!("seeyou_cub_hgt_elevations_{}", std::process::id()));
+        #[rustfmt::skip]
+        write_tile(&directory, "N45E006.hgt", [
+            1000, 1100, 1200,
+             500,  600, HGT_VOID,
+               0,  100,  200,
+        ]);
+
+        let terrain = HgtTerrain::new(&directory);
+
+        let elevation = |lat, lon| assert_some!(terrain.elevation_at(point(lat, lon)));
+
+        // Close to the corners and at the center of the tile
+        assert_lt!((elevation(45.9999, 6.0001) - 1000.).abs(), 1.);
+        assert_lt!((elevation(45.0001, 6.0001) - 0.).abs(), 1.);
+        assert_lt!((elevation(45.5, 6.5) - 600.).abs(), 0.5);
+
+        // Halfway between samples in the north-west quadrant
+        assert_lt!((elevation(45.75, 6.25) - 800.).abs(), 0.5);
+
+        // Next to the void sample
+        assert_none!(terrain.elevation_at(point(45.75, 6.75)));
+
+        // Missing tile
+        assert_none!(terrain.elevation_at(point(47.5, 6.5)));
+
+        std::fs::remove_dir_all(&directory).expect("Failed to remove temporary directory");
+    }
+
+    #[test]
+    fn invalid_tiles() {
+        assert_none!(HgtTile::from_bytes(&[0; 17]));
+        assert_none!(HgtTile::from_bytes(&[0; 16]));
+        assert_some!(HgtTile::from_bytes(&[0; 18]));
+    }
+}
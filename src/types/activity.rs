@@ -0,0 +1,109 @@
+use crate::{DateTime, DaysActive};
+
+/// Result of an activity query like `Airspace::is_active_at()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Activity {
+    /// The airspace is active
+    Active,
+    /// The airspace is not active
+    Inactive,
+    /// The airspace may be active, depending on information that is not part
+    /// of the file (public holidays, AUP, NOTAM or irregular activation)
+    Maybe,
+}
+
+impl Activity {
+    /// Check if the airspace is or may be active
+    ///
+    /// Useful for briefings and warnings, which should err on the safe side.
+    pub fn is_possible(&self) -> bool {
+        *self != Activity::Inactive
+    }
+}
+
+/// Combine the activation window and weekday flags of an airspace
+///
+/// The window is half-open (`start <= time < end`), with `None` meaning no
+/// limit. Unknown weekday flags (all bits cleared) do not restrict activity.
+pub(crate) fn activity_at(
+    start: Option<&DateTime>,
+    end: Option<&DateTime>,
+    days: &DaysActive,
+    time: &DateTime,
+) -> Activity {
+    if start.is_some_and(|start| time < start) || end.is_some_and(|end| time >= end) {
+        return Activity::Inactive;
+    }
+
+    if days.is_unknown() || days.is_active_on(time.weekday()) {
+        Activity::Active
+    } else if days.holidays() || days.aup() || days.irregular() || days.by_notam() {
+        Activity::Maybe
+    } else {
+        Activity::Inactive
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(year: u32, month: u8, day: u8, hour: u8) -> DateTime {
+        DateTime {
+            day,
+            month,
+            year,
+            hour,
+            minute: 0,
+        }
+    }
+
+    // 2024-07-02 is a Tuesday
+    const TUESDAY: u16 = 0x004;
+    const SATURDAY: u16 = 0x040;
+    const HOLIDAYS: u16 = 0x080;
+    const AUP: u16 = 0x100;
+
+    #[test]
+    fn window() {
+        let days = DaysActive::from_bits(0);
+        let start = time(2024, 7, 1, 8);
+        let end = time(2024, 7, 3, 16);
+
+        let activity = |t| activity_at(Some(&start), Some(&end), &days, &t);
+        assert_eq!(activity(time(2024, 6, 30, 12)), Activity::Inactive);
+        assert_eq!(activity(time(2024, 7, 1, 8)), Activity::Active);
+        assert_eq!(activity(time(2024, 7, 2, 12)), Activity::Active);
+        assert_eq!(activity(time(2024, 7, 3, 16)), Activity::Inactive);
+
+        // Chronological, not field order
+        assert_eq!(activity(time(2023, 7, 2, 12)), Activity::Inactive);
+        assert_eq!(activity(time(2024, 8, 2, 12)), Activity::Inactive);
+
+        // Open-ended windows
+        let t = time(2030, 1, 1, 0);
+        assert_eq!(activity_at(Some(&start), None, &days, &t), Activity::Active);
+        assert_eq!(activity_at(None, Some(&end), &days, &t), Activity::Inactive);
+    }
+
+    #[test]
+    fn weekdays() {
+        let tuesday = time(2024, 7, 2, 12);
+        let activity = |bits| activity_at(None, None, &DaysActive::from_bits(bits), &tuesday);
+
+        assert_eq!(activity(0), Activity::Active);
+        assert_eq!(activity(TUESDAY), Activity::Active);
+        assert_eq!(activity(TUESDAY | HOLIDAYS), Activity::Active);
+        assert_eq!(activity(SATURDAY), Activity::Inactive);
+        assert_eq!(activity(SATURDAY | HOLIDAYS), Activity::Maybe);
+        assert_eq!(activity(AUP), Activity::Maybe);
+    }
+
+    #[test]
+    fn window_takes_precedence() {
+        let days = DaysActive::from_bits(AUP);
+        let end = time(2024, 7, 1, 0);
+        let t = time(2024, 7, 2, 12);
+        assert_eq!(activity_at(None, Some(&end), &days, &t), Activity::Inactive);
+    }
+}
@@ -1,4 +1,10 @@
-use crate::{AltStyle, BoundingBox, CubClass, CubStyle, DateTime, DaysActive, ExtendedType, Point};
+use crate::error::Result;
+use crate::terrain::TerrainProvider;
+use crate::types::activity_at;
+use crate::{
+    Activity, AltStyle, BoundingBox, CubClass, CubStyle, DateTime, DaysActive, ExtendedType,
+    NotamInfo, Point, VerticalLimit,
+};
 
 /// High-level airspace representation with fully decoded data
 ///
@@ -39,8 +45,7 @@ pub struct Airspace {
     pub exception_rules: Option<String>,
     pub notam_id: Option<String>,
     pub notam_remarks: Option<String>,
-    pub notam_insert_time: Option<u32>,
-
+    pub notam_insert_time: Option<DateTime>,
     pub extra_data: u32,
 }
 
@@ -50,10 +55,233 @@ impl Airspace {
         (self.extra_data >> 30) == 0 && self.extra_data != 0
     }
 
+    /// Get all NOTAM information if ExtraData contains losslessly decodable
+    /// NOTAM data
+    pub fn notam_info(&self) -> Option<NotamInfo> {
+        NotamInfo::from_extra_data(self.extra_data)
+    }
+
+    /// Set NOTAM information (inverse of `notam_info()`)
+    ///
+    /// `None` clears the ExtraData field. Returns `Error::InvalidNotam` and
+    /// leaves the field unchanged if the NOTAM codes can't be encoded.
+    pub fn set_notam_info(&mut self, notam_info: Option<NotamInfo>) -> Result<()> {
+        self.extra_data = notam_info.map_or(Ok(0), |info| info.to_extra_data())?;
+        Ok(())
+    }
+
     /// Get bounding box
     pub fn bounding_box(&self) -> Option<&BoundingBox> {
         self.bounding_box.as_ref()
     }
+
+    /// Check if the airspace is active at the given time (UTC)
+    ///
+    /// Combines the `start_date`/`end_date` window (start inclusive, end
+    /// exclusive) with the weekday flags in `days_active`. If the weekday flag
+    /// is not set, but the airspace can also be activated on public holidays,
+    /// by AUP, by NOTAM or irregularly, `Activity::Maybe` is returned. Unknown
+    /// days (no flags set) do not restrict activity.
+    pub fn is_active_at(&self, time: DateTime) -> Activity {
+        activity_at(
+            self.start_date.as_ref(),
+            self.end_date.as_ref(),
+            &self.days_active,
+            &time,
+        )
+    }
+
+    /// Get the lower vertical limit
+    ///
+    /// Returns `None` if the altitude style is `AltStyle::Unknown`.
+    pub fn lower_limit(&self) -> Option<VerticalLimit> {
+        VerticalLimit::from_raw(self.min_alt, self.min_alt_style)
+    }
+
+    /// Get the upper vertical limit
+    ///
+    /// Returns `None` if the altitude style is `AltStyle::Unknown`.
+    pub fn upper_limit(&self) -> Option<VerticalLimit> {
+        VerticalLimit::from_raw(self.max_alt, self.max_alt_style)
+    }
+
+    /// Resolve the lower vertical limit to meters above mean sea level
+    ///
+    /// See `VerticalLimit::to_msl()` for how flight levels (via `qnh` in hPa)
+    /// and AGL limits (via `terrain_elevation` in meters MSL) are resolved.
+    /// Returns `None` if the limit cannot be resolved.
+    pub fn lower_limit_msl(&self, qnh: f32, terrain_elevation: Option<f32>) -> Option<f32> {
+        self.lower_limit()?.to_msl(qnh, terrain_elevation)
+    }
+
+    /// Resolve the upper vertical limit to meters above mean sea level
+    ///
+    /// See `VerticalLimit::to_msl()` for how flight levels (via `qnh` in hPa)
+    /// and AGL limits (via `terrain_elevation` in meters MSL) are resolved.
+    /// Returns `None` if the limit cannot be resolved, and `f32::INFINITY`
+    /// for unlimited airspaces.
+    pub fn upper_limit_msl(&self, qnh: f32, terrain_elevation: Option<f32>) -> Option<f32> {
+        self.upper_limit()?.to_msl(qnh, terrain_elevation)
+    }
+
+    /// Resolve the lower vertical limit to meters MSL at `point`
+    ///
+    /// Like `lower_limit_msl()`, but looks up the terrain elevation at `point`
+    /// from `terrain` to resolve AGL limits.
+    pub fn lower_limit_msl_at<T: TerrainProvider + ?Sized>(
+        &self,
+        qnh: f32,
+        point: Point,
+        terrain: &T,
+    ) -> Option<f32> {
+        let limit = self.lower_limit()?;
+        limit.to_msl(qnh, terrain_elevation(limit, point, terrain))
+    }
+
+    /// Resolve the upper vertical limit to meters MSL at `point`
+    ///
+    /// Like `upper_limit_msl()`, but looks up the terrain elevation at `point`
+    /// from `terrain` to resolve AGL limits.
+    pub fn upper_limit_msl_at<T: TerrainProvider + ?Sized>(
+        &self,
+        qnh: f32,
+        point: Point,
+        terrain: &T,
+    ) -> Option<f32> {
+        let limit = self.upper_limit()?;
+        limit.to_msl(qnh, terrain_elevation(limit, point, terrain))
+    }
+
+    /// Set `min_alt` and `min_alt_style` from a vertical limit
+    pub fn set_lower_limit(&mut self, limit: VerticalLimit) {
+        (self.min_alt, self.min_alt_style) = limit.to_raw();
+    }
+
+    /// Set `max_alt` and `max_alt_style` from a vertical limit
+    pub fn set_upper_limit(&mut self, limit: VerticalLimit) {
+        (self.max_alt, self.max_alt_style) = limit.to_raw();
+    }
+
+    /// Check if a point lies inside the lateral airspace boundary
+    ///
+    /// Points outside of the bounding box are rejected without looking at the
+    /// polygon. Otherwise an even-odd ray casting test is performed on the
+    /// lat/lon coordinates. Airspaces with fewer than 3 points never contain
+    /// any point.
+    pub fn contains(&self, point: Point) -> bool {
+        if self.points.len() < 3 {
+            return false;
+        }
+
+        let bbox = self
+            .bounding_box
+            .or_else(|| BoundingBox::from_points(&self.points));
+        if bbox.is_some_and(|bbox| !bbox.contains(point)) {
+            return false;
+        }
+
+        let mut inside = false;
+        let mut prev = self.points[self.points.len() - 1];
+        for &curr in &self.points {
+            if (curr.lat > point.lat) != (prev.lat > point.lat) {
+                let lon_at_lat = (prev.lon - curr.lon) * (point.lat - curr.lat)
+                    / (prev.lat - curr.lat)
+                    + curr.lon;
+                if point.lon < lon_at_lat {
+                    inside = !inside;
+                }
+            }
+            prev = curr;
+        }
+
+        inside
+    }
+
+    /// Check if a point at the given altitude lies inside the airspace
+    ///
+    /// `altitude` is given in meters above mean sea level. The vertical limits
+    /// are interpreted based on their `AltStyle`:
+    ///
+    /// - `MeanSeaLevel`: used as is
+    /// - `FlightLevel`: the stored value is the standard pressure altitude in
+    ///   meters, which is used as is (i.e. QNH 1013.25 hPa is assumed)
+    /// - `AboveGroundLevel`: ground elevation is unknown, so a floor is
+    ///   interpreted relative to sea level (a `0` AGL floor is the surface and
+    ///   matches any altitude) and a ceiling is treated as open, since the
+    ///   ground may be at any elevation
+    /// - `Notam`: the stored value is the standard pressure altitude in meters
+    ///   (as written by `Notam::to_airspace()`), which is used like
+    ///   `FlightLevel`
+    /// - `Unlimited`: no upper limit
+    /// - `Unknown`: the limit is unknown and treated as open (no lower or
+    ///   upper limit), so that warnings err on the safe side
+    ///
+    /// Use `lower_limit_msl()` and `upper_limit_msl()` to take the current QNH
+    /// and terrain elevation into account.
+    pub fn contains_3d(&self, point: Point, altitude: f32) -> bool {
+        let lower = lower_limit_meters(self.min_alt, self.min_alt_style);
+        let upper = upper_limit_meters(self.max_alt, self.max_alt_style);
+
+        altitude >= lower && altitude <= upper && self.contains(point)
+    }
+
+    /// Check if a point at the given altitude lies inside the airspace, using
+    /// the current QNH and terrain elevation
+    ///
+    /// `altitude` is given in meters above mean sea level (e.g. GPS altitude).
+    /// Unlike `contains_3d()`, flight levels are converted with `qnh` (in hPa)
+    /// and AGL limits are resolved with the elevation from `terrain` at
+    /// `point`. Limits that cannot be resolved (unknown style, NOTAM, or no
+    /// terrain data) are treated as open, so that warnings err on the safe
+    /// side. Note that `VerticalLimit::Notam` carries no altitude, so unlike
+    /// `contains_3d()`, NOTAM limits are not resolved here.
+    pub fn contains_3d_with_terrain<T: TerrainProvider + ?Sized>(
+        &self,
+        point: Point,
+        altitude: f32,
+        qnh: f32,
+        terrain: &T,
+    ) -> bool {
+        let lower = self.lower_limit_msl_at(qnh, point, terrain);
+        let upper = self.upper_limit_msl_at(qnh, point, terrain);
+
+        altitude >= lower.unwrap_or(f32::NEG_INFINITY)
+            && altitude <= upper.unwrap_or(f32::INFINITY)
+            && self.contains(point)
+    }
+}
+
+/// Look up the terrain elevation only for limits that depend on it
+fn terrain_elevation<T: TerrainProvider + ?Sized>(
+    limit: VerticalLimit,
+    point: Point,
+    terrain: &T,
+) -> Option<f32> {
+    match limit {
+        VerticalLimit::Surface | VerticalLimit::Agl(_) => terrain.elevation_at(point),
+        _ => None,
+    }
+}
+
+/// Resolve a lower vertical limit to meters MSL for `Airspace::contains_3d()`
+fn lower_limit_meters(alt: i16, style: AltStyle) -> f32 {
+    match style {
+        AltStyle::MeanSeaLevel | AltStyle::FlightLevel | AltStyle::Notam => alt as f32,
+        AltStyle::AboveGroundLevel if alt <= 0 => f32::NEG_INFINITY,
+        AltStyle::AboveGroundLevel => alt as f32,
+        AltStyle::Unlimited => f32::INFINITY,
+        AltStyle::Unknown => f32::NEG_INFINITY,
+    }
+}
+
+/// Resolve an upper vertical limit to meters MSL for `Airspace::contains_3d()`
+fn upper_limit_meters(alt: i16, style: AltStyle) -> f32 {
+    match style {
+        AltStyle::MeanSeaLevel | AltStyle::FlightLevel | AltStyle::Notam => alt as f32,
+        // The ceiling is above `alt` MSL over any terrain above sea level
+        AltStyle::AboveGroundLevel => f32::INFINITY,
+        AltStyle::Unlimited | AltStyle::Unknown => f32::INFINITY,
+    }
 }
 
 impl Default for Airspace {
@@ -84,3 +312,255 @@ impl Default for Airspace {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::assert_lt;
+
+    fn square() -> Airspace {
+        Airspace {
+            points: vec![
+                Point::lat_lon(0.0, 0.0),
+                Point::lat_lon(0.0, 0.1),
+                Point::lat_lon(0.1, 0.1),
+                Point::lat_lon(0.1, 0.0),
+            ],
+            min_alt: 0,
+            max_alt: 1500,
+            min_alt_style: AltStyle::AboveGroundLevel,
+            max_alt_style: AltStyle::MeanSeaLevel,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn vertical_limits() {
+        let mut airspace = square();
+        assert_eq!(airspace.lower_limit(), Some(VerticalLimit::Surface));
+        assert_eq!(airspace.upper_limit(), Some(VerticalLimit::Msl(1500)));
+
+        airspace.set_upper_limit(VerticalLimit::FlightLevel(95));
+        assert_eq!(airspace.max_alt, 2896);
+        assert_eq!(airspace.max_alt_style, AltStyle::FlightLevel);
+        assert_eq!(airspace.upper_limit(), Some(VerticalLimit::FlightLevel(95)));
+
+        airspace.min_alt_style = AltStyle::Unknown;
+        assert_eq!(airspace.lower_limit(), None);
+    }
+
+    #[test]
+    fn vertical_limits_msl() {
+        let mut airspace = square();
+        airspace.set_upper_limit(VerticalLimit::FlightLevel(65));
+
+        assert_eq!(airspace.lower_limit_msl(1013.25, Some(320.)), Some(320.));
+        assert_eq!(airspace.lower_limit_msl(1013.25, None), None);
+
+        let standard = airspace.upper_limit_msl(1013.25, None).unwrap();
+        let high = airspace.upper_limit_msl(1030., None).unwrap();
+        assert_lt!((standard - 1981.).abs(), 1.);
+        assert_lt!((high - standard - 132.).abs(), 2.);
+    }
+
+    #[test]
+    fn contains_3d_with_terrain() {
+        let mut airspace = square();
+        airspace.set_lower_limit(VerticalLimit::Agl(300));
+        airspace.set_upper_limit(VerticalLimit::FlightLevel(65));
+
+        let inside = Point::lat_lon(0.05, 0.05);
+        let terrain = |_: Point| Some(1000.);
+        assert_eq!(
+            airspace.lower_limit_msl_at(1013.25, inside, &terrain),
+            Some(1300.)
+        );
+
+        assert!(!airspace.contains_3d_with_terrain(inside, 1200., 1013.25, &terrain));
+        assert!(airspace.contains_3d_with_terrain(inside, 1400., 1013.25, &terrain));
+        assert!(airspace.contains_3d_with_terrain(inside, 1950., 1013.25, &terrain));
+        assert!(!airspace.contains_3d_with_terrain(inside, 2000., 1013.25, &terrain));
+
+        // Higher QNH lifts FL65 above 2000 m MSL
+        assert!(airspace.contains_3d_with_terrain(inside, 2000., 1030., &terrain));
+
+        // Without terrain data the AGL floor is treated as open
+        let unknown = |_: Point| None;
+        assert!(airspace.contains_3d_with_terrain(inside, 500., 1013.25, &unknown));
+    }
+
+    #[test]
+    fn is_active_at() {
+        let time = |day| DateTime {
+            day,
+            month: 7,
+            year: 2024,
+            hour: 12,
+            minute: 0,
+        };
+
+        // Weekends only, from 2024-07-01 on
+        let airspace = Airspace {
+            start_date: Some(time(1)),
+            days_active: DaysActive::from_bits(0x001 | 0x040),
+            ..square()
+        };
+
+        assert_eq!(airspace.is_active_at(time(2)), Activity::Inactive);
+        assert_eq!(airspace.is_active_at(time(6)), Activity::Active);
+        assert_eq!(airspace.is_active_at(time(7)), Activity::Active);
+        assert!(!airspace.is_active_at(time(8)).is_possible());
+    }
+
+    #[test]
+    fn contains_point_inside() {
+        let airspace = square();
+        assert!(airspace.contains(Point::lat_lon(0.05, 0.05)));
+        assert!(airspace.contains(Point::lat_lon(0.01, 0.09)));
+    }
+
+    #[test]
+    fn contains_point_outside() {
+        let airspace = square();
+        assert!(!airspace.contains(Point::lat_lon(0.2, 0.05)));
+        assert!(!airspace.contains(Point::lat_lon(0.05, -0.01)));
+    }
+
+    #[test]
+    fn contains_concave_polygon() {
+        // "U" shape opening to the north
+        let airspace = Airspace {
+            points: vec![
+                Point::lat_lon(0.0, 0.0),
+                Point::lat_lon(0.0, 0.3),
+                Point::lat_lon(0.3, 0.3),
+                Point::lat_lon(0.3, 0.2),
+                Point::lat_lon(0.1, 0.2),
+                Point::lat_lon(0.1, 0.1),
+                Point::lat_lon(0.3, 0.1),
+                Point::lat_lon(0.3, 0.0),
+            ],
+            ..Default::default()
+        };
+
+        assert!(airspace.contains(Point::lat_lon(0.05, 0.15)));
+        assert!(airspace.contains(Point::lat_lon(0.2, 0.05)));
+        assert!(!airspace.contains(Point::lat_lon(0.2, 0.15))); // Inside the notch
+    }
+
+    #[test]
+    fn contains_uses_bounding_box_reject() {
+        // A bounding box that excludes part of the polygon wins
+        let airspace = Airspace {
+            bounding_box: Some(BoundingBox {
+                left: 0.0,
+                top: 0.05,
+                right: 0.05,
+                bottom: 0.0,
+            }),
+            ..square()
+        };
+
+        assert!(airspace.contains(Point::lat_lon(0.01, 0.01)));
+        assert!(!airspace.contains(Point::lat_lon(0.09, 0.09)));
+    }
+
+    #[test]
+    fn contains_requires_three_points() {
+        let airspace = Airspace {
+            points: vec![Point::lat_lon(0.0, 0.0), Point::lat_lon(0.1, 0.1)],
+            ..Default::default()
+        };
+
+        assert!(!airspace.contains(Point::lat_lon(0.05, 0.05)));
+    }
+
+    #[test]
+    fn contains_3d_msl_limits() {
+        let airspace = Airspace {
+            min_alt: 500,
+            min_alt_style: AltStyle::MeanSeaLevel,
+            ..square()
+        };
+        let point = Point::lat_lon(0.05, 0.05);
+
+        assert!(!airspace.contains_3d(point, 499.));
+        assert!(airspace.contains_3d(point, 500.));
+        assert!(airspace.contains_3d(point, 1500.));
+        assert!(!airspace.contains_3d(point, 1501.));
+        assert!(!airspace.contains_3d(Point::lat_lon(0.2, 0.2), 1000.));
+    }
+
+    #[test]
+    fn contains_3d_surface_and_flight_level() {
+        // SFC - FL195 (stored as 5944 m)
+        let airspace = Airspace {
+            max_alt: 5944,
+            max_alt_style: AltStyle::FlightLevel,
+            ..square()
+        };
+        let point = Point::lat_lon(0.05, 0.05);
+
+        assert!(airspace.contains_3d(point, -100.));
+        assert!(airspace.contains_3d(point, 5944.));
+        assert!(!airspace.contains_3d(point, 6000.));
+    }
+
+    #[test]
+    fn contains_3d_agl_ceiling() {
+        // SFC - 1000 m AGL, e.g. over terrain at 2000 m MSL
+        let airspace = Airspace {
+            max_alt: 1000,
+            max_alt_style: AltStyle::AboveGroundLevel,
+            ..square()
+        };
+        let point = Point::lat_lon(0.05, 0.05);
+
+        // Terrain is unknown, so the ceiling must not cut off at 1000 m MSL
+        assert!(airspace.contains_3d(point, 2500.));
+
+        let terrain = |_: Point| Some(2000.);
+        assert!(airspace.contains_3d_with_terrain(point, 2500., 1013.25, &terrain));
+        assert!(!airspace.contains_3d_with_terrain(point, 3100., 1013.25, &terrain));
+    }
+
+    #[test]
+    fn contains_3d_unlimited_and_unknown() {
+        let airspace = Airspace {
+            min_alt: 3000,
+            min_alt_style: AltStyle::MeanSeaLevel,
+            max_alt_style: AltStyle::Unlimited,
+            ..square()
+        };
+        let point = Point::lat_lon(0.05, 0.05);
+
+        assert!(!airspace.contains_3d(point, 2999.));
+        assert!(airspace.contains_3d(point, 20_000.));
+
+        let airspace = Airspace {
+            min_alt_style: AltStyle::Unknown,
+            max_alt_style: AltStyle::Unknown,
+            ..square()
+        };
+
+        assert!(airspace.contains_3d(point, -500.));
+        assert!(airspace.contains_3d(point, 20_000.));
+    }
+
+    #[test]
+    fn contains_3d_notam_limits() {
+        // FL050 - FL095 as written by `Notam::to_airspace()`
+        let airspace = Airspace {
+            min_alt: 1524,
+            max_alt: 2896,
+            min_alt_style: AltStyle::Notam,
+            max_alt_style: AltStyle::Notam,
+            ..square()
+        };
+        let point = Point::lat_lon(0.05, 0.05);
+
+        assert!(!airspace.contains_3d(point, 1500.));
+        assert!(airspace.contains_3d(point, 2000.));
+        assert!(!airspace.contains_3d(point, 3000.));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::error::Result;
 use crate::types::Point;
+use crate::types::point::EARTH_RADIUS;
 use crate::utils::io::{read_f32_le, write_f32_le};
 use std::io::{Read, Write};
 
@@ -92,6 +93,52 @@ impl BoundingBox {
         self.top = self.top.max(other.top);
         self.bottom = self.bottom.min(other.bottom);
     }
+
+    /// Check if a point lies inside or on the edge of the bounding box
+    pub fn contains(&self, point: Point) -> bool {
+        point.lon >= self.left
+            && point.lon <= self.right
+            && point.lat >= self.bottom
+            && point.lat <= self.top
+    }
+
+    /// Check if this bounding box overlaps with another one
+    ///
+    /// Boxes that only touch at an edge or corner are considered intersecting.
+    pub fn intersects(&self, other: &BoundingBox) -> bool {
+        self.left <= other.right
+            && self.right >= other.left
+            && self.bottom <= other.top
+            && self.top >= other.bottom
+    }
+
+    /// Calculate the great-circle distance from a point to the bounding box in meters
+    ///
+    /// Returns `0.0` if the point is inside the bounding box. Otherwise the
+    /// distance to the nearest point on the box edge is returned.
+    pub fn distance_to(&self, point: Point) -> f32 {
+        let nearest = Point::lat_lon(
+            point.lat.clamp(self.bottom, self.top),
+            point.lon.clamp(self.left, self.right),
+        );
+        point.distance_to(nearest)
+    }
+
+    /// Create a bounding box covering a circle around a point
+    ///
+    /// `radius` is given in meters. The longitude extent is widened by the
+    /// latitude of the center, so the result always contains the whole circle.
+    pub fn around(center: Point, radius: f32) -> Self {
+        let d_lat = radius / EARTH_RADIUS;
+        let d_lon = d_lat / center.lat.cos().max(f32::EPSILON);
+
+        Self {
+            left: center.lon - d_lon,
+            top: center.lat + d_lat,
+            right: center.lon + d_lon,
+            bottom: center.lat - d_lat,
+        }
+    }
 }
 
 impl From<Point> for BoundingBox {
@@ -109,7 +156,7 @@ impl From<Point> for BoundingBox {
 mod tests {
     use super::*;
     use crate::types::Point;
-    use claims::assert_none;
+    use claims::{assert_lt, assert_none};
     use std::io::Cursor;
 
     #[test]
@@ -379,6 +426,85 @@ mod tests {
         assert_eq!(bbox1.bottom, 0.0);
     }
 
+    #[test]
+    fn test_contains() {
+        let bbox = BoundingBox {
+            left: 0.0,
+            top: 1.0,
+            right: 1.0,
+            bottom: 0.0,
+        };
+
+        assert!(bbox.contains(Point::lat_lon(0.5, 0.5)));
+        assert!(bbox.contains(Point::lat_lon(1.0, 0.0))); // Corner
+        assert!(!bbox.contains(Point::lat_lon(1.1, 0.5))); // North
+        assert!(!bbox.contains(Point::lat_lon(0.5, -0.1))); // West
+    }
+
+    #[test]
+    fn test_intersects() {
+        let bbox = BoundingBox {
+            left: 0.0,
+            top: 1.0,
+            right: 1.0,
+            bottom: 0.0,
+        };
+
+        let overlapping = BoundingBox {
+            left: 0.5,
+            top: 1.5,
+            right: 1.5,
+            bottom: 0.5,
+        };
+        let touching = BoundingBox {
+            left: 1.0,
+            top: 1.0,
+            right: 2.0,
+            bottom: 0.0,
+        };
+        let disjoint = BoundingBox {
+            left: 1.1,
+            top: 1.0,
+            right: 2.0,
+            bottom: 0.0,
+        };
+
+        assert!(bbox.intersects(&overlapping));
+        assert!(overlapping.intersects(&bbox));
+        assert!(bbox.intersects(&touching));
+        assert!(!bbox.intersects(&disjoint));
+        assert!(!disjoint.intersects(&bbox));
+    }
+
+    #[test]
+    fn test_distance_to() {
+        let bbox = BoundingBox {
+            left: 0.0,
+            top: 0.01,
+            right: 0.01,
+            bottom: 0.0,
+        };
+
+        // Inside
+        assert_eq!(bbox.distance_to(Point::lat_lon(0.005, 0.005)), 0.0);
+
+        // Directly north of the box: 0.01 rad of latitude ≈ 63.7 km
+        let distance = bbox.distance_to(Point::lat_lon(0.02, 0.005));
+        assert_lt!((distance - 63_710.).abs(), 10.);
+    }
+
+    #[test]
+    fn test_around() {
+        let center = Point::lat_lon(0.8, 0.1);
+        let bbox = BoundingBox::around(center, 10_000.);
+
+        assert!(bbox.contains(center));
+        assert_lt!(bbox.distance_to(Point::lat_lon(0.8, bbox.left)), 10_001.);
+
+        // Longitude extent is wider than latitude extent away from the equator
+        assert_lt!(bbox.top - bbox.bottom, bbox.right - bbox.left);
+    }
+
     #[test]
     fn test_basic_construction() {
         let bbox = BoundingBox {
@@ -1,5 +1,16 @@
-/// Simple date and time representation
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+use crate::error::{Error, Result};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Simple date and time representation (UTC)
+///
+/// The fields are not validated on construction. Use `DateTime::new()` to
+/// reject dates that don't exist or can't be stored in CUB files.
+///
+/// With the `chrono`, `time` or `jiff` cargo features enabled, `DateTime`
+/// can be converted to and from the date and time types of these crates.
+/// Seconds and smaller units are truncated when converting to `DateTime`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct DateTime {
     pub day: u8,
     pub month: u8,
@@ -7,3 +18,475 @@ pub struct DateTime {
     pub hour: u8,
     pub minute: u8,
 }
+
+impl DateTime {
+    /// Earliest year that can be stored in CUB files
+    pub const MIN_YEAR: u32 = 2000;
+
+    /// Latest year that can be stored in CUB files
+    pub const MAX_YEAR: u32 = 2124;
+
+    /// Create a new date and time, checking that it is valid
+    ///
+    /// Returns `Error::InvalidDateTime` if the date doesn't exist (e.g.
+    /// February 30), the time is out of range, or the year is outside of
+    /// `MIN_YEAR..=MAX_YEAR`.
+    ///
+    /// ```
+    /// use seeyou_cub::DateTime;
+    ///
+    /// assert!(DateTime::new(2024, 2, 29, 12, 0).is_ok());
+    /// assert!(DateTime::new(2023, 2, 29, 12, 0).is_err());
+    /// assert!(DateTime::new(1999, 12, 31, 12, 0).is_err());
+    /// ```
+    pub fn new(year: u32, month: u8, day: u8, hour: u8, minute: u8) -> Result<Self> {
+        let date_time = Self {
+            day,
+            month,
+            year,
+            hour,
+            minute,
+        };
+
+        if !date_time.is_valid() {
+            return Err(Error::InvalidDateTime(date_time.to_string()));
+        }
+
+        Ok(date_time)
+    }
+
+    /// Check if this is an existing date and time that can be stored in CUB
+    /// files
+    pub fn is_valid(&self) -> bool {
+        (Self::MIN_YEAR..=Self::MAX_YEAR).contains(&self.year)
+            && (1..=12).contains(&self.month)
+            && (1..=days_in_month(self.year, self.month)).contains(&self.day)
+            && self.hour < 24
+            && self.minute < 60
+    }
+
+    /// Get the day of the week
+    ///
+    /// Uses the proleptic Gregorian calendar. Out-of-range months are clamped
+    /// to `1..=12`.
+    pub fn weekday(&self) -> Weekday {
+        // Sakamoto's method, 0 = Sunday
+        const MONTH_OFFSETS: [u32; 12] = [0, 3, 2, 5, 0, 3, 5, 1, 4, 6, 2, 4];
+
+        let month = self.month.clamp(1, 12);
+        let year = if month < 3 {
+            self.year.saturating_sub(1)
+        } else {
+            self.year
+        };
+
+        let day = (year + year / 4 - year / 100
+            + year / 400
+            + MONTH_OFFSETS[month as usize - 1]
+            + self.day as u32)
+            % 7;
+
+        Weekday::ALL[day as usize]
+    }
+}
+
+impl fmt::Display for DateTime {
+    /// Format as `YYYY-MM-DD hh:mm`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute
+        )
+    }
+}
+
+impl Ord for DateTime {
+    /// Compare chronologically, i.e. by year, month, day, hour and minute
+    fn cmp(&self, other: &Self) -> Ordering {
+        let key = |time: &Self| (time.year, time.month, time.day, time.hour, time.minute);
+        key(self).cmp(&key(other))
+    }
+}
+
+impl PartialOrd for DateTime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn is_leap_year(year: u32) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+fn days_in_month(year: u32, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Create a `DateTime` from the fields of another crate's date and time
+/// type, using `value` for the error message
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+fn from_fields(
+    value: impl fmt::Display,
+    year: i32,
+    month: u8,
+    day: u8,
+    hour: u8,
+    minute: u8,
+) -> Result<DateTime> {
+    u32::try_from(year)
+        .ok()
+        .and_then(|year| DateTime::new(year, month, day, hour, minute).ok())
+        .ok_or_else(|| Error::InvalidDateTime(value.to_string()))
+}
+
+/// Check that `date_time` is valid before converting it to another crate's
+/// date and time type
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+fn checked(date_time: DateTime) -> Result<DateTime> {
+    DateTime::new(
+        date_time.year,
+        date_time.month,
+        date_time.day,
+        date_time.hour,
+        date_time.minute,
+    )
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use super::{DateTime, checked, from_fields};
+    use crate::error::Error;
+    use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike, Utc};
+
+    impl TryFrom<NaiveDateTime> for DateTime {
+        type Error = Error;
+
+        fn try_from(value: NaiveDateTime) -> Result<Self, Error> {
+            from_fields(
+                value,
+                value.year(),
+                value.month() as u8,
+                value.day() as u8,
+                value.hour() as u8,
+                value.minute() as u8,
+            )
+        }
+    }
+
+    impl TryFrom<chrono::DateTime<Utc>> for DateTime {
+        type Error = Error;
+
+        fn try_from(value: chrono::DateTime<Utc>) -> Result<Self, Error> {
+            value.naive_utc().try_into()
+        }
+    }
+
+    impl TryFrom<DateTime> for NaiveDateTime {
+        type Error = Error;
+
+        fn try_from(value: DateTime) -> Result<Self, Error> {
+            let value = checked(value)?;
+            NaiveDate::from_ymd_opt(value.year as i32, value.month as u32, value.day as u32)
+                .and_then(|date| date.and_hms_opt(value.hour as u32, value.minute as u32, 0))
+                .ok_or_else(|| Error::InvalidDateTime(value.to_string()))
+        }
+    }
+
+    impl TryFrom<DateTime> for chrono::DateTime<Utc> {
+        type Error = Error;
+
+        fn try_from(value: DateTime) -> Result<Self, Error> {
+            NaiveDateTime::try_from(value).map(|value| value.and_utc())
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use super::{DateTime, checked, from_fields};
+    use crate::error::Error;
+    use time::{Date, Month, PrimitiveDateTime, Time, UtcDateTime};
+
+    impl TryFrom<PrimitiveDateTime> for DateTime {
+        type Error = Error;
+
+        fn try_from(value: PrimitiveDateTime) -> Result<Self, Error> {
+            from_fields(
+                value,
+                value.year(),
+                value.month().into(),
+                value.day(),
+                value.hour(),
+                value.minute(),
+            )
+        }
+    }
+
+    impl TryFrom<UtcDateTime> for DateTime {
+        type Error = Error;
+
+        fn try_from(value: UtcDateTime) -> Result<Self, Error> {
+            PrimitiveDateTime::new(value.date(), value.time()).try_into()
+        }
+    }
+
+    impl TryFrom<DateTime> for PrimitiveDateTime {
+        type Error = Error;
+
+        fn try_from(value: DateTime) -> Result<Self, Error> {
+            let value = checked(value)?;
+            let invalid = |_| Error::InvalidDateTime(value.to_string());
+
+            let month = Month::try_from(value.month).map_err(invalid)?;
+            let date =
+                Date::from_calendar_date(value.year as i32, month, value.day).map_err(invalid)?;
+            let time = Time::from_hms(value.hour, value.minute, 0).map_err(invalid)?;
+            Ok(PrimitiveDateTime::new(date, time))
+        }
+    }
+
+    impl TryFrom<DateTime> for UtcDateTime {
+        type Error = Error;
+
+        fn try_from(value: DateTime) -> Result<Self, Error> {
+            PrimitiveDateTime::try_from(value).map(|value| value.as_utc())
+        }
+    }
+}
+
+#[cfg(feature = "jiff")]
+mod jiff_impls {
+    use super::{DateTime, checked, from_fields};
+    use crate::error::Error;
+    use jiff::Timestamp;
+    use jiff::tz::Offset;
+
+    impl TryFrom<jiff::civil::DateTime> for DateTime {
+        type Error = Error;
+
+        fn try_from(value: jiff::civil::DateTime) -> Result<Self, Error> {
+            from_fields(
+                value,
+                value.year().into(),
+                value.month() as u8,
+                value.day() as u8,
+                value.hour() as u8,
+                value.minute() as u8,
+            )
+        }
+    }
+
+    impl TryFrom<Timestamp> for DateTime {
+        type Error = Error;
+
+        fn try_from(value: Timestamp) -> Result<Self, Error> {
+            Offset::UTC.to_datetime(value).try_into()
+        }
+    }
+
+    impl TryFrom<DateTime> for jiff::civil::DateTime {
+        type Error = Error;
+
+        fn try_from(value: DateTime) -> Result<Self, Error> {
+            let value = checked(value)?;
+            jiff::civil::DateTime::new(
+                value.year as i16,
+                value.month as i8,
+                value.day as i8,
+                value.hour as i8,
+                value.minute as i8,
+                0,
+                0,
+            )
+            .map_err(|_| Error::InvalidDateTime(value.to_string()))
+        }
+    }
+
+    impl TryFrom<DateTime> for Timestamp {
+        type Error = Error;
+
+        fn try_from(value: DateTime) -> Result<Self, Error> {
+            let date_time = jiff::civil::DateTime::try_from(value)?;
+            Offset::UTC
+                .to_timestamp(date_time)
+                .map_err(|_| Error::InvalidDateTime(value.to_string()))
+        }
+    }
+}
+
+/// Day of the week
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Weekday {
+    Sunday,
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+}
+
+impl Weekday {
+    /// All days of the week, starting with Sunday (like `DaysActive`)
+    pub const ALL: [Weekday; 7] = [
+        Weekday::Sunday,
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+    ];
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_err, assert_lt, assert_matches, assert_ok_eq};
+
+    fn date(year: u32, month: u8, day: u8) -> DateTime {
+        DateTime {
+            day,
+            month,
+            year,
+            hour: 12,
+            minute: 0,
+        }
+    }
+
+    #[test]
+    fn weekday() {
+        assert_eq!(date(2000, 1, 1).weekday(), Weekday::Saturday);
+        assert_eq!(date(2024, 2, 29).weekday(), Weekday::Thursday);
+        assert_eq!(date(2024, 7, 2).weekday(), Weekday::Tuesday);
+        assert_eq!(date(2024, 12, 31).weekday(), Weekday::Tuesday);
+        assert_eq!(date(2026, 10, 18).weekday(), Weekday::Sunday);
+    }
+
+    #[test]
+    fn chronological_order() {
+        assert_lt!(date(2024, 7, 2), date(2024, 7, 3));
+        assert_lt!(date(2024, 6, 30), date(2024, 7, 1));
+        assert_lt!(date(2023, 12, 31), date(2024, 1, 1));
+
+        let mut evening = date(2024, 7, 2);
+        evening.hour = 18;
+        assert_lt!(date(2024, 7, 2), evening);
+        assert_lt!(evening, date(2024, 7, 3));
+    }
+
+    #[test]
+    fn checked_construction() {
+        assert_ok_eq!(
+            DateTime::new(2024, 2, 29, 23, 59),
+            date(2024, 2, 29).with_time(23, 59)
+        );
+        assert_ok_eq!(
+            DateTime::new(2000, 1, 1, 0, 0),
+            date(2000, 1, 1).with_time(0, 0)
+        );
+        assert_ok_eq!(
+            DateTime::new(2124, 12, 31, 0, 0),
+            date(2124, 12, 31).with_time(0, 0)
+        );
+
+        for (year, month, day, hour, minute) in [
+            (2023, 2, 29, 12, 0),
+            (2100, 2, 29, 12, 0),
+            (2024, 2, 30, 12, 0),
+            (2024, 4, 31, 12, 0),
+            (2024, 0, 1, 12, 0),
+            (2024, 13, 1, 12, 0),
+            (2024, 1, 0, 12, 0),
+            (2024, 1, 1, 24, 0),
+            (2024, 1, 1, 12, 60),
+            (1999, 12, 31, 12, 0),
+            (2125, 1, 1, 0, 0),
+        ] {
+            let result = DateTime::new(year, month, day, hour, minute);
+            assert_matches!(result, Err(Error::InvalidDateTime(_)));
+        }
+
+        let error = assert_err!(DateTime::new(2024, 2, 30, 9, 5));
+        assert_eq!(error.to_string(), "Invalid date/time: 2024-02-30 09:05");
+    }
+
+    impl DateTime {
+        fn with_time(self, hour: u8, minute: u8) -> Self {
+            Self {
+                hour,
+                minute,
+                ..self
+            }
+        }
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_conversions() {
+        use chrono::{NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+        let utc = Utc.with_ymd_and_hms(2024, 7, 2, 14, 30, 45).unwrap();
+        let converted = assert_ok_eq!(DateTime::try_from(utc), date(2024, 7, 2).with_time(14, 30));
+        assert_ok_eq!(
+            chrono::DateTime::<Utc>::try_from(converted),
+            Utc.with_ymd_and_hms(2024, 7, 2, 14, 30, 0).unwrap()
+        );
+
+        let naive = NaiveDate::from_ymd_opt(1999, 12, 31)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        assert_err!(DateTime::try_from(naive));
+        assert_err!(NaiveDateTime::try_from(date(2023, 2, 29)));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_conversions() {
+        use time::{Date, Month, PrimitiveDateTime, Time, UtcDateTime};
+
+        let date_part = Date::from_calendar_date(2024, Month::July, 2).unwrap();
+        let utc = UtcDateTime::new(date_part, Time::from_hms(14, 30, 45).unwrap());
+        let converted = assert_ok_eq!(DateTime::try_from(utc), date(2024, 7, 2).with_time(14, 30));
+        assert_ok_eq!(
+            UtcDateTime::try_from(converted),
+            UtcDateTime::new(date_part, Time::from_hms(14, 30, 0).unwrap())
+        );
+
+        let early = Date::from_calendar_date(1999, Month::December, 31).unwrap();
+        assert_err!(DateTime::try_from(PrimitiveDateTime::new(
+            early,
+            Time::MIDNIGHT
+        )));
+        assert_err!(PrimitiveDateTime::try_from(date(2023, 2, 29)));
+    }
+
+    #[cfg(feature = "jiff")]
+    #[test]
+    fn jiff_conversions() {
+        use jiff::Timestamp;
+        use jiff::civil;
+
+        let timestamp: Timestamp = "2024-07-02T14:30:45Z".parse().unwrap();
+        let converted = assert_ok_eq!(
+            DateTime::try_from(timestamp),
+            date(2024, 7, 2).with_time(14, 30)
+        );
+        assert_ok_eq!(
+            Timestamp::try_from(converted),
+            "2024-07-02T14:30:00Z".parse::<Timestamp>().unwrap()
+        );
+
+        assert_err!(DateTime::try_from(civil::datetime(
+            1999, 12, 31, 0, 0, 0, 0
+        )));
+        assert_err!(civil::DateTime::try_from(date(2023, 2, 29)));
+    }
+}
@@ -0,0 +1,518 @@
+use crate::Weekday;
+use crate::error::Error;
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign};
+use std::str::FromStr;
+
+/// Days active flags (bits 52-63 of ActiveTime)
+///
+/// A set of weekdays plus the holiday, AUP, irregular and NOTAM activation
+/// flags. Sets can be built from `Weekday` and `DaysActiveFlag` values and
+/// combined with `|` (union) and `&` (intersection):
+///
+/// ```
+/// use seeyou_cub::{DaysActive, DaysActiveFlag, Weekday};
+///
+/// let days = DaysActive::weekdays().with(DaysActiveFlag::Holidays) | Weekday::Saturday.into();
+/// assert_eq!(days.to_string(), "Mon-Sat, HOL");
+/// assert_eq!("Mon-Sat, HOL".parse::<DaysActive>()?, days);
+///
+/// assert_eq!(days & DaysActive::weekends(), Weekday::Saturday.into());
+/// # Ok::<(), seeyou_cub::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DaysActive {
+    bits: u16,
+}
+
+/// Bits of all `DaysActiveFlag` values
+const FLAG_BITS: u16 = 0x7FF;
+
+impl DaysActive {
+    /// Create a set from raw bits
+    ///
+    /// Bits that do not correspond to a `DaysActiveFlag` (e.g. the reserved
+    /// bit 11) are kept, so that they are written back unchanged, but are
+    /// ignored by `iter()`, `is_unknown()` and `Display`.
+    pub fn from_bits(bits: u16) -> Self {
+        Self { bits }
+    }
+
+    /// Create a set from raw bits, dropping bits without a `DaysActiveFlag`
+    pub fn from_bits_truncate(bits: u16) -> Self {
+        Self {
+            bits: bits & FLAG_BITS,
+        }
+    }
+
+    pub fn as_bits(&self) -> u64 {
+        self.bits as u64
+    }
+
+    /// Empty set, which is also used for unknown activity
+    pub fn empty() -> Self {
+        Self { bits: 0 }
+    }
+
+    /// Every day of the week (Sunday-Saturday), without any other flags
+    pub fn all() -> Self {
+        Self { bits: 0x7F } // All days active
+    }
+
+    /// Monday-Friday
+    pub fn weekdays() -> Self {
+        Self { bits: 0x3E }
+    }
+
+    /// Saturday and Sunday
+    pub fn weekends() -> Self {
+        Self { bits: 0x41 }
+    }
+
+    /// Return a copy with the given days or flags added
+    pub fn with(self, days: impl Into<DaysActive>) -> Self {
+        self | days.into()
+    }
+
+    /// Return a copy with the given days or flags removed
+    pub fn without(self, days: impl Into<DaysActive>) -> Self {
+        Self {
+            bits: self.bits & !days.into().bits,
+        }
+    }
+
+    /// Check if all of the given days or flags are set
+    pub fn contains(&self, days: impl Into<DaysActive>) -> bool {
+        let bits = days.into().bits;
+        self.bits & bits == bits
+    }
+
+    /// Iterate over the set flags, starting with Sunday
+    pub fn iter(&self) -> impl Iterator<Item = DaysActiveFlag> + use<> {
+        let days = *self;
+        DaysActiveFlag::ALL
+            .into_iter()
+            .filter(move |&flag| days.contains(flag))
+    }
+
+    pub fn sunday(&self) -> bool {
+        self.bits & 0x001 != 0
+    }
+    pub fn monday(&self) -> bool {
+        self.bits & 0x002 != 0
+    }
+    pub fn tuesday(&self) -> bool {
+        self.bits & 0x004 != 0
+    }
+    pub fn wednesday(&self) -> bool {
+        self.bits & 0x008 != 0
+    }
+    pub fn thursday(&self) -> bool {
+        self.bits & 0x010 != 0
+    }
+    pub fn friday(&self) -> bool {
+        self.bits & 0x020 != 0
+    }
+    pub fn saturday(&self) -> bool {
+        self.bits & 0x040 != 0
+    }
+    pub fn holidays(&self) -> bool {
+        self.bits & 0x080 != 0
+    }
+    pub fn aup(&self) -> bool {
+        self.bits & 0x100 != 0
+    }
+    pub fn irregular(&self) -> bool {
+        self.bits & 0x200 != 0
+    }
+    pub fn by_notam(&self) -> bool {
+        self.bits & 0x400 != 0
+    }
+    pub fn is_unknown(&self) -> bool {
+        self.bits & FLAG_BITS == 0
+    }
+
+    /// Check if the flag for the given day of the week is set
+    pub fn is_active_on(&self, weekday: Weekday) -> bool {
+        self.bits & (1 << weekday as u16) != 0
+    }
+}
+
+/// A single flag of `DaysActive`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DaysActiveFlag {
+    Day(Weekday),
+    /// Active on public holidays
+    Holidays,
+    /// Activated by the Airspace Use Plan
+    Aup,
+    /// Irregular activation
+    Irregular,
+    /// Activated by NOTAM
+    ByNotam,
+}
+
+impl DaysActiveFlag {
+    /// All flags in bit order
+    pub const ALL: [DaysActiveFlag; 11] = [
+        DaysActiveFlag::Day(Weekday::Sunday),
+        DaysActiveFlag::Day(Weekday::Monday),
+        DaysActiveFlag::Day(Weekday::Tuesday),
+        DaysActiveFlag::Day(Weekday::Wednesday),
+        DaysActiveFlag::Day(Weekday::Thursday),
+        DaysActiveFlag::Day(Weekday::Friday),
+        DaysActiveFlag::Day(Weekday::Saturday),
+        DaysActiveFlag::Holidays,
+        DaysActiveFlag::Aup,
+        DaysActiveFlag::Irregular,
+        DaysActiveFlag::ByNotam,
+    ];
+
+    fn bit(&self) -> u16 {
+        match self {
+            DaysActiveFlag::Day(weekday) => 1 << *weekday as u16,
+            DaysActiveFlag::Holidays => 0x080,
+            DaysActiveFlag::Aup => 0x100,
+            DaysActiveFlag::Irregular => 0x200,
+            DaysActiveFlag::ByNotam => 0x400,
+        }
+    }
+}
+
+impl From<Weekday> for DaysActiveFlag {
+    fn from(weekday: Weekday) -> Self {
+        DaysActiveFlag::Day(weekday)
+    }
+}
+
+impl From<DaysActiveFlag> for DaysActive {
+    fn from(flag: DaysActiveFlag) -> Self {
+        Self { bits: flag.bit() }
+    }
+}
+
+impl From<Weekday> for DaysActive {
+    fn from(weekday: Weekday) -> Self {
+        DaysActiveFlag::Day(weekday).into()
+    }
+}
+
+impl FromIterator<DaysActiveFlag> for DaysActive {
+    fn from_iter<I: IntoIterator<Item = DaysActiveFlag>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(DaysActive::empty(), |days, flag| days.with(flag))
+    }
+}
+
+impl BitOr for DaysActive {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self {
+            bits: self.bits | rhs.bits,
+        }
+    }
+}
+
+impl BitOrAssign for DaysActive {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs;
+    }
+}
+
+impl BitAnd for DaysActive {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self {
+            bits: self.bits & rhs.bits,
+        }
+    }
+}
+
+impl BitAndAssign for DaysActive {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = *self & rhs;
+    }
+}
+
+/// Days of the week in schedule order, starting with Monday
+const WEEK: [Weekday; 7] = [
+    Weekday::Monday,
+    Weekday::Tuesday,
+    Weekday::Wednesday,
+    Weekday::Thursday,
+    Weekday::Friday,
+    Weekday::Saturday,
+    Weekday::Sunday,
+];
+
+/// Other flags with their `Display` names
+const OTHER_FLAGS: [(DaysActiveFlag, &str); 4] = [
+    (DaysActiveFlag::Holidays, "HOL"),
+    (DaysActiveFlag::Aup, "AUP"),
+    (DaysActiveFlag::Irregular, "IRREG"),
+    (DaysActiveFlag::ByNotam, "by NOTAM"),
+];
+
+fn abbreviation(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Sunday => "Sun",
+        Weekday::Monday => "Mon",
+        Weekday::Tuesday => "Tue",
+        Weekday::Wednesday => "Wed",
+        Weekday::Thursday => "Thu",
+        Weekday::Friday => "Fri",
+        Weekday::Saturday => "Sat",
+    }
+}
+
+impl fmt::Display for DaysActive {
+    /// Format as a schedule like `Mon-Fri, HOL, by NOTAM`
+    ///
+    /// Consecutive days (Monday to Sunday) are joined into ranges. An empty
+    /// set is formatted as `unknown`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_unknown() {
+            return write!(f, "unknown");
+        }
+
+        let mut parts = Vec::new();
+
+        let mut index = 0;
+        while index < WEEK.len() {
+            if !self.is_active_on(WEEK[index]) {
+                index += 1;
+                continue;
+            }
+
+            let start = index;
+            while index + 1 < WEEK.len() && self.is_active_on(WEEK[index + 1]) {
+                index += 1;
+            }
+
+            parts.push(if index == start {
+                abbreviation(WEEK[start]).to_string()
+            } else {
+                format!(
+                    "{}-{}",
+                    abbreviation(WEEK[start]),
+                    abbreviation(WEEK[index])
+                )
+            });
+            index += 1;
+        }
+
+        for (flag, name) in OTHER_FLAGS {
+            if self.contains(flag) {
+                parts.push(name.to_string());
+            }
+        }
+
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+impl FromStr for DaysActive {
+    type Err = Error;
+
+    /// Parse a schedule like `Mon-Fri, HOL, by NOTAM`
+    ///
+    /// Parsing is case-insensitive. Days may be abbreviated (`Mon`) or
+    /// written out (`Monday`), and ranges may wrap around the end of the week
+    /// (`Sat-Mon`). `unknown` parses to an empty set.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidDaysActive(s.to_string());
+
+        if s.trim().eq_ignore_ascii_case("unknown") {
+            return Ok(DaysActive::empty());
+        }
+
+        let mut days = DaysActive::empty();
+        for part in s.split(',') {
+            let part = part.split_whitespace().collect::<Vec<_>>().join(" ");
+            let part = part.to_ascii_lowercase();
+
+            let flag = match part.as_str() {
+                "hol" | "holidays" => Some(DaysActiveFlag::Holidays),
+                "aup" => Some(DaysActiveFlag::Aup),
+                "irreg" | "irregular" => Some(DaysActiveFlag::Irregular),
+                "by notam" | "notam" => Some(DaysActiveFlag::ByNotam),
+                _ => None,
+            };
+
+            if let Some(flag) = flag {
+                days = days.with(flag);
+            } else if let Some((first, last)) = part.split_once('-') {
+                let first = parse_weekday(first.trim()).ok_or_else(invalid)?;
+                let last = parse_weekday(last.trim()).ok_or_else(invalid)?;
+
+                let start = WEEK.iter().position(|&day| day == first).unwrap_or(0);
+                for &day in WEEK.iter().cycle().skip(start).take(WEEK.len()) {
+                    days = days.with(day);
+                    if day == last {
+                        break;
+                    }
+                }
+            } else {
+                days = days.with(parse_weekday(&part).ok_or_else(invalid)?);
+            }
+        }
+
+        Ok(days)
+    }
+}
+
+/// Parse an abbreviated or full lowercase day name
+fn parse_weekday(name: &str) -> Option<Weekday> {
+    WEEK.into_iter().find(|&day| {
+        let abbreviation = abbreviation(day).to_ascii_lowercase();
+        let full = format!("{day:?}").to_ascii_lowercase();
+        name == abbreviation || name == full
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::{assert_matches, assert_ok_eq};
+
+    #[test]
+    fn days_active() {
+        let days = DaysActive::from_bits(0x001 | 0x004 | 0x040);
+        assert!(days.sunday());
+        assert!(!days.monday());
+        assert!(days.tuesday());
+        assert!(days.saturday());
+        assert!(!days.holidays());
+    }
+
+    #[test]
+    fn builder_and_operators() {
+        let days = DaysActive::empty()
+            .with(Weekday::Monday)
+            .with(Weekday::Wednesday)
+            .with(DaysActiveFlag::ByNotam);
+        assert!(days.monday());
+        assert!(days.wednesday());
+        assert!(days.by_notam());
+        assert!(!days.tuesday());
+        assert_eq!(days.as_bits(), 0x40A);
+
+        assert_eq!(
+            DaysActive::weekdays() | DaysActive::weekends(),
+            DaysActive::all()
+        );
+        assert_eq!(
+            DaysActive::weekdays() & DaysActive::weekends(),
+            DaysActive::empty()
+        );
+        assert_eq!(
+            days & DaysActive::weekdays(),
+            days.without(DaysActiveFlag::ByNotam)
+        );
+
+        let mut days = DaysActive::weekends();
+        days |= Weekday::Friday.into();
+        days &= DaysActive::all().without(Weekday::Sunday);
+        assert_eq!(
+            days,
+            DaysActive::empty()
+                .with(Weekday::Friday)
+                .with(Weekday::Saturday)
+        );
+
+        assert!(DaysActive::all().contains(DaysActive::weekends()));
+        assert!(!DaysActive::all().contains(DaysActiveFlag::Holidays));
+    }
+
+    #[test]
+    fn iterate_flags() {
+        let days = DaysActive::weekends().with(DaysActiveFlag::Aup);
+        let flags: Vec<_> = days.iter().collect();
+        assert_eq!(
+            flags,
+            [
+                DaysActiveFlag::Day(Weekday::Sunday),
+                DaysActiveFlag::Day(Weekday::Saturday),
+                DaysActiveFlag::Aup,
+            ]
+        );
+
+        assert_eq!(flags.into_iter().collect::<DaysActive>(), days);
+        assert_eq!(DaysActive::empty().iter().count(), 0);
+        assert_eq!(DaysActive::from_bits(0x7FF).iter().count(), 11);
+
+        let reserved = DaysActive::from_bits(0xF801);
+        assert_eq!(reserved.as_bits(), 0xF801);
+        assert_eq!(
+            reserved.iter().collect::<Vec<_>>(),
+            [DaysActiveFlag::Day(Weekday::Sunday)]
+        );
+        assert!(DaysActive::from_bits(0xF800).is_unknown());
+        assert_eq!(DaysActive::from_bits_truncate(0xF800), DaysActive::empty());
+    }
+
+    #[test]
+    fn display() {
+        let display = |days: DaysActive| days.to_string();
+
+        assert_eq!(display(DaysActive::empty()), "unknown");
+        assert_eq!(display(DaysActive::all()), "Mon-Sun");
+        assert_eq!(display(DaysActive::weekdays()), "Mon-Fri");
+        assert_eq!(display(DaysActive::weekends()), "Sat-Sun");
+        assert_eq!(
+            display(DaysActive::from_bits(0x002 | 0x008 | 0x010 | 0x020)),
+            "Mon, Wed-Fri"
+        );
+        assert_eq!(
+            display(
+                DaysActive::weekdays()
+                    .with(DaysActiveFlag::Holidays)
+                    .with(DaysActiveFlag::ByNotam)
+            ),
+            "Mon-Fri, HOL, by NOTAM"
+        );
+        assert_eq!(display(DaysActive::from_bits(0x300)), "AUP, IRREG");
+    }
+
+    #[test]
+    fn parse() {
+        let cases = [
+            ("Mon-Fri, HOL, by NOTAM", 0x03E | 0x080 | 0x400),
+            ("mon - fri", 0x03E),
+            ("Saturday, Sunday", 0x041),
+            ("Sat-Mon", 0x043),
+            ("Sun", 0x001),
+            ("AUP, irregular, NOTAM", 0x700),
+            ("unknown", 0),
+        ];
+
+        for (input, bits) in cases {
+            assert_ok_eq!(
+                input.parse::<DaysActive>(),
+                DaysActive::from_bits(bits),
+                "{input}"
+            );
+        }
+
+        for input in ["", "Mon-", "Funday", "Mon-Fri, Xmas"] {
+            assert_matches!(
+                input.parse::<DaysActive>(),
+                Err(Error::InvalidDaysActive(_)),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for bits in 0..=u16::MAX {
+            let days = DaysActive::from_bits(bits);
+            assert_ok_eq!(
+                days.to_string().parse::<DaysActive>(),
+                DaysActive::from_bits_truncate(bits)
+            );
+        }
+    }
+}
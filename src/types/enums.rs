@@ -1,3 +1,5 @@
+use crate::error::{Error, Result};
+
 /// Byte ordering for integer fields
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ByteOrder {
@@ -68,6 +70,41 @@ pub enum CubStyle {
 }
 
 impl CubStyle {
+    /// All known airspace styles
+    pub const ALL: [CubStyle; 31] = [
+        CubStyle::Unknown,
+        CubStyle::ControlZone,
+        CubStyle::RestrictedArea,
+        CubStyle::ProhibitedArea,
+        CubStyle::DangerArea,
+        CubStyle::TemporaryReservedArea,
+        CubStyle::TerminalControlArea,
+        CubStyle::TrafficInformationZone,
+        CubStyle::Airway,
+        CubStyle::ControlArea,
+        CubStyle::GliderSector,
+        CubStyle::TransponderMandatoryZone,
+        CubStyle::MilitaryAerodromeTrafficZone,
+        CubStyle::RadioMandatoryZone,
+        CubStyle::Notam,
+        CubStyle::Advisory,
+        CubStyle::AirDefenceIdentificationZone,
+        CubStyle::FlightInformationRegion,
+        CubStyle::DelegatedFir,
+        CubStyle::TrafficInformationArea,
+        CubStyle::SpecialRulesZone,
+        CubStyle::TemporaryFlightRestriction,
+        CubStyle::AerodromeTrafficZone,
+        CubStyle::FlightInformationServiceArea,
+        CubStyle::LegacyRmz,
+        CubStyle::AerialSportingAndRecreationArea,
+        CubStyle::TransponderRecommendedZone,
+        CubStyle::VfrRoute,
+        CubStyle::Alert,
+        CubStyle::TemporarySegregatedArea,
+        CubStyle::Warning,
+    ];
+
     /// Parse from combined Type byte value (lowest 4 bits + highest bit)
     pub fn from_type_byte(byte: u8) -> Self {
         let value = (byte & 0x0F) | (byte & 0x80);
@@ -165,6 +202,18 @@ pub enum CubClass {
 }
 
 impl CubClass {
+    /// All known airspace classes
+    pub const ALL: [CubClass; 8] = [
+        CubClass::Unknown,
+        CubClass::ClassA,
+        CubClass::ClassB,
+        CubClass::ClassC,
+        CubClass::ClassD,
+        CubClass::ClassE,
+        CubClass::ClassF,
+        CubClass::ClassG,
+    ];
+
     /// Extract from Type byte (bits 5-7)
     pub fn from_type_byte(byte: u8) -> Self {
         let value = (byte >> 4) & 0x07;
@@ -213,6 +262,16 @@ pub enum AltStyle {
 }
 
 impl AltStyle {
+    /// All known altitude styles
+    pub const ALL: [AltStyle; 6] = [
+        AltStyle::Unknown,
+        AltStyle::AboveGroundLevel,
+        AltStyle::MeanSeaLevel,
+        AltStyle::FlightLevel,
+        AltStyle::Unlimited,
+        AltStyle::Notam,
+    ];
+
     /// Parse from 4-bit value
     pub fn from_nibble(value: u8) -> Self {
         match value & 0x0F {
@@ -255,6 +314,20 @@ pub enum ExtendedType {
 }
 
 impl ExtendedType {
+    /// All known extended airspace types
+    pub const ALL: [ExtendedType; 10] = [
+        ExtendedType::UpperInfoRegion,
+        ExtendedType::MilitaryTrainingRoute,
+        ExtendedType::HelicopterTrafficZone,
+        ExtendedType::AreaControlCenterSector,
+        ExtendedType::LowerTrafficArea,
+        ExtendedType::UpperTrafficArea,
+        ExtendedType::MilitaryTrainingArea,
+        ExtendedType::OverflightRestriction,
+        ExtendedType::TraTsaFeedingRoute,
+        ExtendedType::VfrSector,
+    ];
+
     pub fn from_byte(byte: u8) -> Option<Self> {
         match byte {
             0x01 => Some(ExtendedType::UpperInfoRegion),
@@ -306,6 +379,17 @@ impl NotamType {
             _ => NotamType::None,
         }
     }
+
+    /// Encode into ExtraData bits (inverse of `from_bits()`)
+    pub fn to_bits(&self) -> u32 {
+        let value = match self {
+            NotamType::None => 0,
+            NotamType::Cancel => 1,
+            NotamType::New => 2,
+            NotamType::Replace => 3,
+        };
+        value << 28
+    }
 }
 
 /// NOTAM traffic type (from ExtraData bits 4-6)
@@ -329,6 +413,18 @@ impl NotamTraffic {
             _ => NotamTraffic::Miscellaneous,
         }
     }
+
+    /// Encode into ExtraData bits (inverse of `from_bits()`)
+    pub fn to_bits(&self) -> u32 {
+        let value = match self {
+            NotamTraffic::Miscellaneous => 0,
+            NotamTraffic::Ifr => 1,
+            NotamTraffic::Vfr => 2,
+            NotamTraffic::IfrAndVfr => 3,
+            NotamTraffic::Checklist => 4,
+        };
+        value << 4
+    }
 }
 
 /// NOTAM scope (from ExtraData bits 0-3)
@@ -356,62 +452,18 @@ impl NotamScope {
             _ => NotamScope::Unknown,
         }
     }
-}
-
-/// Days active flags (bits 52-63 of ActiveTime)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct DaysActive {
-    bits: u16,
-}
-
-impl DaysActive {
-    pub fn from_bits(bits: u16) -> Self {
-        Self { bits }
-    }
 
-    pub fn as_bits(&self) -> u64 {
-        self.bits as u64
-    }
-
-    pub fn all() -> Self {
-        Self { bits: 0x7F } // All days active
-    }
-
-    pub fn sunday(&self) -> bool {
-        self.bits & 0x001 != 0
-    }
-    pub fn monday(&self) -> bool {
-        self.bits & 0x002 != 0
-    }
-    pub fn tuesday(&self) -> bool {
-        self.bits & 0x004 != 0
-    }
-    pub fn wednesday(&self) -> bool {
-        self.bits & 0x008 != 0
-    }
-    pub fn thursday(&self) -> bool {
-        self.bits & 0x010 != 0
-    }
-    pub fn friday(&self) -> bool {
-        self.bits & 0x020 != 0
-    }
-    pub fn saturday(&self) -> bool {
-        self.bits & 0x040 != 0
-    }
-    pub fn holidays(&self) -> bool {
-        self.bits & 0x080 != 0
-    }
-    pub fn aup(&self) -> bool {
-        self.bits & 0x100 != 0
-    }
-    pub fn irregular(&self) -> bool {
-        self.bits & 0x200 != 0
-    }
-    pub fn by_notam(&self) -> bool {
-        self.bits & 0x400 != 0
-    }
-    pub fn is_unknown(&self) -> bool {
-        self.bits == 0
+    /// Encode into ExtraData bits (inverse of `from_bits()`)
+    pub fn to_bits(&self) -> u32 {
+        match self {
+            NotamScope::Unknown => 0,
+            NotamScope::Aerodrome => 1,
+            NotamScope::EnRoute => 2,
+            NotamScope::AerodromeAndEnRoute => 3,
+            NotamScope::NavWarning => 4,
+            NotamScope::AerodromeAndNavWarning => 5,
+            NotamScope::Checklist => 8,
+        }
     }
 }
 
@@ -452,13 +504,29 @@ impl CubDataId {
 }
 
 /// NOTAM subject and action codes (decoded from ExtraData)
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct NotamCodes {
     pub subject: (char, char), // First and last letter
     pub action: (char, char),  // First and last letter
 }
 
 impl NotamCodes {
+    /// Create NOTAM codes from subject and action letters
+    ///
+    /// Lowercase letters are converted to uppercase. Returns
+    /// `Error::InvalidNotam` if any of the characters is not an ASCII letter.
+    pub fn new(subject: (char, char), action: (char, char)) -> Result<Self> {
+        let codes = Self {
+            subject: (
+                subject.0.to_ascii_uppercase(),
+                subject.1.to_ascii_uppercase(),
+            ),
+            action: (action.0.to_ascii_uppercase(), action.1.to_ascii_uppercase()),
+        };
+        codes.validate()?;
+        Ok(codes)
+    }
+
     /// Decode from ExtraData field (bits 8-27 encode letters as 1-26)
     pub fn from_extra_data(extra_data: u32) -> Option<Self> {
         // Check if this is NOTAM data (bits 30-31 == 0)
@@ -483,11 +551,39 @@ impl NotamCodes {
             action: (action_first, action_last),
         })
     }
+
+    /// Encode into ExtraData bits 8-27 (inverse of `from_extra_data()`)
+    ///
+    /// Returns `Error::InvalidNotam` if any of the letters is not an uppercase
+    /// ASCII letter, since it could not be decoded again.
+    pub fn to_extra_data(&self) -> Result<u32> {
+        self.validate()?;
+
+        let encode_letter = |letter: char| letter as u32 - 'A' as u32 + 1;
+        Ok((encode_letter(self.subject.0) << 23)
+            | (encode_letter(self.subject.1) << 18)
+            | (encode_letter(self.action.0) << 13)
+            | (encode_letter(self.action.1) << 8))
+    }
+
+    /// Check that all letters are uppercase ASCII letters
+    fn validate(&self) -> Result<()> {
+        let (s1, s2) = self.subject;
+        let (a1, a2) = self.action;
+        if ![s1, s2, a1, a2].iter().all(char::is_ascii_uppercase) {
+            return Err(Error::InvalidNotam(format!(
+                "invalid NOTAM code letters: {s1}{s2}{a1}{a2}"
+            )));
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use claims::{assert_matches, assert_ok, assert_ok_eq};
 
     #[test]
     fn byte_order_from_pc_byte_order() {
@@ -529,16 +625,6 @@ mod tests {
         assert_eq!(NotamType::from_bits(0b11 << 28), NotamType::Replace);
     }
 
-    #[test]
-    fn days_active() {
-        let days = DaysActive::from_bits(0x001 | 0x004 | 0x040);
-        assert!(days.sunday());
-        assert!(!days.monday());
-        assert!(days.tuesday());
-        assert!(days.saturday());
-        assert!(!days.holidays());
-    }
-
     #[test]
     fn notam_codes_decode() {
         // Example: subject "AA", action "BB"
@@ -550,5 +636,36 @@ mod tests {
         let codes = NotamCodes::from_extra_data(extra_data).unwrap();
         assert_eq!(codes.subject, ('A', 'A'));
         assert_eq!(codes.action, ('B', 'B'));
+        assert_ok_eq!(codes.to_extra_data(), extra_data);
+    }
+
+    #[test]
+    fn notam_codes_validation() {
+        let codes = assert_ok!(NotamCodes::new(('r', 't'), ('C', 'a')));
+        assert_eq!(codes.subject, ('R', 'T'));
+        assert_eq!(codes.action, ('C', 'A'));
+
+        assert_matches!(
+            NotamCodes::new(('R', '1'), ('C', 'A')),
+            Err(Error::InvalidNotam(_))
+        );
+        assert_matches!(
+            NotamCodes::new(('R', 'T'), ('C', 'Ä')),
+            Err(Error::InvalidNotam(_))
+        );
+
+        // Invalid letters can't be encoded losslessly
+        for codes in [
+            NotamCodes {
+                subject: ('R', '1'),
+                action: ('C', 'A'),
+            },
+            NotamCodes {
+                subject: ('r', 't'),
+                action: ('C', 'A'),
+            },
+        ] {
+            assert_matches!(codes.to_extra_data(), Err(Error::InvalidNotam(_)));
+        }
     }
 }
@@ -1,11 +1,20 @@
+mod activity;
 mod airspace;
 mod bounding_box;
 mod datetime;
+mod days_active;
 mod enums;
+mod notam_info;
 mod point;
+mod vertical_limit;
 
+pub use activity::Activity;
+pub(crate) use activity::activity_at;
 pub use airspace::*;
 pub use bounding_box::*;
 pub use datetime::*;
+pub use days_active::*;
 pub use enums::*;
+pub use notam_info::*;
 pub use point::*;
+pub use vertical_limit::*;
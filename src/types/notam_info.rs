@@ -0,0 +1,132 @@
+use crate::error::Result;
+use crate::{NotamCodes, NotamScope, NotamTraffic, NotamType};
+
+/// NOTAM information stored in the ExtraData field
+///
+/// ExtraData contains NOTAM data if it is not 0 and the highest two bits are
+/// not set. `NotamInfo::from_extra_data()` only accepts values that
+/// `NotamInfo::to_extra_data()` encodes back to the exact same bits.
+///
+/// ```
+/// use seeyou_cub::{NotamCodes, NotamInfo, NotamScope, NotamTraffic, NotamType};
+///
+/// let info = NotamInfo {
+///     notam_type: NotamType::New,
+///     codes: Some(NotamCodes {
+///         subject: ('R', 'D'),
+///         action: ('C', 'A'),
+///     }),
+///     traffic: NotamTraffic::IfrAndVfr,
+///     scope: NotamScope::NavWarning,
+/// };
+///
+/// let extra_data = info.to_extra_data()?;
+/// assert_eq!(NotamInfo::from_extra_data(extra_data), Some(info));
+/// # Ok::<(), seeyou_cub::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotamInfo {
+    pub notam_type: NotamType,
+    /// Q-code subject and action letters
+    pub codes: Option<NotamCodes>,
+    pub traffic: NotamTraffic,
+    pub scope: NotamScope,
+}
+
+impl NotamInfo {
+    /// Decode from ExtraData field
+    ///
+    /// Returns `None` if the field contains no NOTAM data or values that
+    /// can't be represented losslessly (e.g. undefined scopes or partial
+    /// Q-codes).
+    pub fn from_extra_data(extra_data: u32) -> Option<Self> {
+        // Check if this is NOTAM data (bits 30-31 == 0)
+        if (extra_data >> 30) != 0 || extra_data == 0 {
+            return None;
+        }
+
+        let info = Self {
+            notam_type: NotamType::from_bits(extra_data),
+            codes: NotamCodes::from_extra_data(extra_data),
+            traffic: NotamTraffic::from_bits(extra_data),
+            scope: NotamScope::from_bits(extra_data),
+        };
+
+        (info.to_extra_data().ok() == Some(extra_data)).then_some(info)
+    }
+
+    /// Encode into ExtraData field (inverse of `from_extra_data()`)
+    ///
+    /// Returns 0 (no NOTAM data) if all fields have their default values, or
+    /// `Error::InvalidNotam` if the codes can't be encoded (see
+    /// `NotamCodes::to_extra_data()`).
+    pub fn to_extra_data(&self) -> Result<u32> {
+        let codes = self
+            .codes
+            .as_ref()
+            .map_or(Ok(0), NotamCodes::to_extra_data)?;
+
+        Ok(self.notam_type.to_bits() | codes | self.traffic.to_bits() | self.scope.to_bits())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    use claims::{assert_matches, assert_none, assert_ok, assert_some_eq};
+
+    #[test]
+    fn round_trip() {
+        let codes = NotamCodes {
+            subject: ('W', 'E'),
+            action: ('L', 'W'),
+        };
+
+        let info = NotamInfo {
+            notam_type: NotamType::Replace,
+            codes: Some(codes),
+            traffic: NotamTraffic::Checklist,
+            scope: NotamScope::Checklist,
+        };
+
+        let extra_data = assert_ok!(info.to_extra_data());
+        assert_eq!(extra_data, 0x3B95_9748);
+        assert_some_eq!(NotamInfo::from_extra_data(extra_data), info);
+
+        let info = NotamInfo {
+            codes: None,
+            ..info
+        };
+        assert_some_eq!(
+            NotamInfo::from_extra_data(assert_ok!(info.to_extra_data())),
+            info
+        );
+
+        let info = NotamInfo {
+            codes: Some(NotamCodes {
+                subject: ('W', '?'),
+                action: ('L', 'W'),
+            }),
+            ..info
+        };
+        assert_matches!(info.to_extra_data(), Err(Error::InvalidNotam(_)));
+    }
+
+    #[test]
+    fn lossy_values_are_rejected() {
+        // No NOTAM data
+        assert_none!(NotamInfo::from_extra_data(0));
+        assert_none!(NotamInfo::from_extra_data(0x4000_0001));
+
+        // Undefined scope and traffic values
+        assert_none!(NotamInfo::from_extra_data(0x0000_0006));
+        assert_none!(NotamInfo::from_extra_data(0x0000_0050));
+
+        // Unused bit 7
+        assert_none!(NotamInfo::from_extra_data(0x0000_0081));
+
+        // Partial Q-code
+        assert_none!(NotamInfo::from_extra_data(1 << 23));
+    }
+}
@@ -1,3 +1,6 @@
+/// Mean Earth radius in meters, used for great-circle distance calculations
+pub(crate) const EARTH_RADIUS: f32 = 6_371_000.0;
+
 /// A geographic coordinate point
 ///
 /// Represents a single point in an airspace boundary with lat/lon coordinates in radians.
@@ -23,6 +26,48 @@ impl Point {
             && self.lon >= -std::f32::consts::PI
             && self.lon <= std::f32::consts::PI
     }
+
+    /// Calculate the great-circle distance to another point in meters
+    ///
+    /// Uses the haversine formula on a spherical Earth model, which is accurate
+    /// to within about 0.5% for aviation purposes.
+    pub fn distance_to(&self, other: Point) -> f32 {
+        let d_lat = other.lat - self.lat;
+        let d_lon = other.lon - self.lon;
+
+        let a = (d_lat / 2.).sin().powi(2)
+            + self.lat.cos() * other.lat.cos() * (d_lon / 2.).sin().powi(2);
+
+        2. * EARTH_RADIUS * a.sqrt().min(1.).asin()
+    }
+
+    /// Calculate the initial great-circle bearing to another point
+    ///
+    /// Returns the bearing in radians, clockwise from true north, in the
+    /// range `0..2π`.
+    pub fn bearing_to(&self, other: Point) -> f32 {
+        let d_lon = other.lon - self.lon;
+
+        let y = d_lon.sin() * other.lat.cos();
+        let x = self.lat.cos() * other.lat.sin() - self.lat.sin() * other.lat.cos() * d_lon.cos();
+
+        y.atan2(x).rem_euclid(std::f32::consts::TAU)
+    }
+
+    /// Calculate the point at a given distance and bearing from this point
+    ///
+    /// `bearing` is given in radians clockwise from true north, `distance` in
+    /// meters along the great circle.
+    pub fn destination(&self, bearing: f32, distance: f32) -> Point {
+        let d = distance / EARTH_RADIUS;
+
+        let lat = (self.lat.sin() * d.cos() + self.lat.cos() * d.sin() * bearing.cos()).asin();
+        let lon = self.lon
+            + (bearing.sin() * d.sin() * self.lat.cos())
+                .atan2(d.cos() - self.lat.sin() * lat.sin());
+
+        Point::lat_lon(lat, lon)
+    }
 }
 
 impl std::fmt::Debug for Point {
@@ -34,6 +79,8 @@ impl std::fmt::Debug for Point {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use claims::assert_lt;
+    use std::f32::consts::{FRAC_PI_2, PI};
 
     #[test]
     fn point_creation() {
@@ -56,4 +103,47 @@ mod tests {
         assert!(!Point::lat_lon(0.0, std::f32::consts::PI + 0.1).is_valid()); // Invalid lon
         assert!(!Point::lat_lon(0.0, -std::f32::consts::PI - 0.1).is_valid()); // Invalid lon
     }
+
+    #[test]
+    fn point_distance_to() {
+        // Paris: 48.8566°N, 2.3522°E / London: 51.5074°N, 0.1278°W
+        let paris = Point::lat_lon(48.8566_f32.to_radians(), 2.3522_f32.to_radians());
+        let london = Point::lat_lon(51.5074_f32.to_radians(), (-0.1278_f32).to_radians());
+
+        // ~343.5 km
+        let distance = paris.distance_to(london);
+        assert_lt!((distance - 343_500.).abs(), 1_000.);
+        assert_eq!(paris.distance_to(paris), 0.);
+    }
+
+    #[test]
+    fn point_bearing_to() {
+        let origin = Point::lat_lon(0., 0.);
+
+        assert_lt!(origin.bearing_to(Point::lat_lon(0.01, 0.)).abs(), 1e-6); // North
+        assert_lt!(
+            (origin.bearing_to(Point::lat_lon(0., 0.01)) - FRAC_PI_2).abs(),
+            1e-6
+        ); // East
+        assert_lt!(
+            (origin.bearing_to(Point::lat_lon(-0.01, 0.)) - PI).abs(),
+            1e-6
+        ); // South
+        assert_lt!(
+            (origin.bearing_to(Point::lat_lon(0., -0.01)) - 3. * FRAC_PI_2).abs(),
+            1e-6
+        ); // West
+    }
+
+    #[test]
+    fn point_destination() {
+        // Paris: 48.8566°N, 2.3522°E
+        let paris = Point::lat_lon(48.8566_f32.to_radians(), 2.3522_f32.to_radians());
+
+        for bearing in [0., FRAC_PI_2, PI, 4.] {
+            let dest = paris.destination(bearing, 10_000.);
+            assert_lt!((paris.distance_to(dest) - 10_000.).abs(), 1.);
+            assert_lt!((paris.bearing_to(dest) - bearing).abs(), 1e-3);
+        }
+    }
 }
@@ -0,0 +1,327 @@
+use crate::AltStyle;
+use crate::error::Error;
+use crate::utils::units::{METERS_PER_FOOT, flight_level_to_meters, pressure_altitude_to_msl};
+use std::fmt;
+use std::str::FromStr;
+
+/// Vertical limit of an airspace, combining the altitude value and its reference
+///
+/// The CUB format stores each limit as an `i16` value in meters plus an
+/// `AltStyle`. Flight levels are stored as the corresponding standard pressure
+/// altitude in meters, which `VerticalLimit` converts back to the flight level.
+///
+/// `Display` uses aviation notation (e.g. `GND`, `1500 ft AMSL`, `FL95`),
+/// which can be parsed back via `FromStr`.
+///
+/// # Example
+///
+/// ```
+/// use seeyou_cub::{AltStyle, VerticalLimit};
+///
+/// let limit = VerticalLimit::from_raw(2896, AltStyle::FlightLevel);
+/// assert_eq!(limit, Some(VerticalLimit::FlightLevel(95)));
+/// assert_eq!(limit.unwrap().to_string(), "FL95");
+///
+/// let limit: VerticalLimit = "1500 ft AMSL".parse()?;
+/// assert_eq!(limit, VerticalLimit::Msl(457));
+/// assert_eq!(limit.to_raw(), (457, AltStyle::MeanSeaLevel));
+/// # Ok::<(), seeyou_cub::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VerticalLimit {
+    /// Ground or water surface
+    Surface,
+    /// Altitude above mean sea level in meters
+    Msl(i16),
+    /// Height above ground level in meters
+    Agl(i16),
+    /// Flight level (hundreds of feet at standard pressure)
+    FlightLevel(u16),
+    /// No upper limit
+    Unlimited,
+    /// Limit is published by NOTAM
+    Notam,
+}
+
+impl VerticalLimit {
+    /// Convert from the raw altitude value and style stored in the CUB file
+    ///
+    /// `0` AGL is returned as `Surface`. Returns `None` for
+    /// `AltStyle::Unknown`, since the value cannot be interpreted.
+    pub fn from_raw(alt: i16, style: AltStyle) -> Option<Self> {
+        match style {
+            AltStyle::Unknown => None,
+            AltStyle::AboveGroundLevel if alt == 0 => Some(VerticalLimit::Surface),
+            AltStyle::AboveGroundLevel => Some(VerticalLimit::Agl(alt)),
+            AltStyle::MeanSeaLevel => Some(VerticalLimit::Msl(alt)),
+            AltStyle::FlightLevel => {
+                let level = (alt as f32 / METERS_PER_FOOT / 100.).round().max(0.);
+                Some(VerticalLimit::FlightLevel(level as u16))
+            }
+            AltStyle::Unlimited => Some(VerticalLimit::Unlimited),
+            AltStyle::Notam => Some(VerticalLimit::Notam),
+        }
+    }
+
+    /// Convert to the raw altitude value and style stored in the CUB file
+    ///
+    /// Flight levels are converted to the standard pressure altitude in
+    /// meters, `Unlimited` is stored as `i16::MAX`.
+    pub fn to_raw(&self) -> (i16, AltStyle) {
+        match *self {
+            VerticalLimit::Surface => (0, AltStyle::AboveGroundLevel),
+            VerticalLimit::Msl(meters) => (meters, AltStyle::MeanSeaLevel),
+            VerticalLimit::Agl(meters) => (meters, AltStyle::AboveGroundLevel),
+            VerticalLimit::FlightLevel(level) => {
+                let meters = flight_level_to_meters(level).round();
+                (meters.min(i16::MAX as f32) as i16, AltStyle::FlightLevel)
+            }
+            VerticalLimit::Unlimited => (i16::MAX, AltStyle::Unlimited),
+            VerticalLimit::Notam => (0, AltStyle::Notam),
+        }
+    }
+
+    /// Resolve the limit to meters above mean sea level
+    ///
+    /// Flight levels are converted with the ICAO standard atmosphere for the
+    /// given `qnh` (in hPa, see `utils::units::pressure_altitude_to_msl()`).
+    /// `Surface` and AGL limits are resolved relative to `terrain_elevation`
+    /// (in meters MSL), and `Unlimited` is returned as `f32::INFINITY`.
+    ///
+    /// Returns `None` for `Notam` limits, and for `Surface` and AGL limits if
+    /// no terrain elevation is given.
+    pub fn to_msl(&self, qnh: f32, terrain_elevation: Option<f32>) -> Option<f32> {
+        match *self {
+            VerticalLimit::Surface => terrain_elevation,
+            VerticalLimit::Msl(meters) => Some(meters as f32),
+            VerticalLimit::Agl(meters) => terrain_elevation.map(|ground| ground + meters as f32),
+            VerticalLimit::FlightLevel(level) => {
+                Some(pressure_altitude_to_msl(flight_level_to_meters(level), qnh))
+            }
+            VerticalLimit::Unlimited => Some(f32::INFINITY),
+            VerticalLimit::Notam => None,
+        }
+    }
+}
+
+impl From<VerticalLimit> for (i16, AltStyle) {
+    fn from(limit: VerticalLimit) -> Self {
+        limit.to_raw()
+    }
+}
+
+impl fmt::Display for VerticalLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            VerticalLimit::Surface => write!(f, "GND"),
+            VerticalLimit::Msl(meters) => write!(f, "{} ft AMSL", to_feet(meters)),
+            VerticalLimit::Agl(meters) => write!(f, "{} ft AGL", to_feet(meters)),
+            VerticalLimit::FlightLevel(level) => write!(f, "FL{level}"),
+            VerticalLimit::Unlimited => write!(f, "UNL"),
+            VerticalLimit::Notam => write!(f, "NOTAM"),
+        }
+    }
+}
+
+impl FromStr for VerticalLimit {
+    type Err = Error;
+
+    /// Parse a vertical limit like `GND`, `FL95`, `1500 ft AMSL` or `300 m AGL`
+    ///
+    /// Parsing is case-insensitive and ignores whitespace. Feet are assumed if
+    /// no unit is given, while the reference (`AMSL`/`MSL`/`ALT` or
+    /// `AGL`/`GND`/`SFC`/`ASFC`) is required for altitudes.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidVerticalLimit(s.to_string());
+
+        let normalized: String = s
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_uppercase();
+
+        match normalized.as_str() {
+            "GND" | "SFC" | "SURFACE" => return Ok(VerticalLimit::Surface),
+            "UNL" | "UNLIM" | "UNLIMITED" => return Ok(VerticalLimit::Unlimited),
+            "NOTAM" => return Ok(VerticalLimit::Notam),
+            _ => {}
+        }
+
+        if let Some(level) = normalized.strip_prefix("FL") {
+            let level = level.parse().map_err(|_| invalid())?;
+            if flight_level_to_meters(level).round() > i16::MAX as f32 {
+                return Err(invalid());
+            }
+            return Ok(VerticalLimit::FlightLevel(level));
+        }
+
+        let number_len = normalized
+            .find(|c: char| !c.is_ascii_digit() && c != '.' && c != '-')
+            .unwrap_or(normalized.len());
+        let (number, rest) = normalized.split_at(number_len);
+        let number: f32 = number.parse().map_err(|_| invalid())?;
+
+        let (factor, reference) = if let Some(rest) = rest.strip_prefix("FT") {
+            (METERS_PER_FOOT, rest)
+        } else if let Some(rest) = rest.strip_prefix('M').filter(|_| !rest.starts_with("MSL")) {
+            (1., rest)
+        } else if let Some(rest) = rest.strip_prefix('F') {
+            (METERS_PER_FOOT, rest)
+        } else {
+            (METERS_PER_FOOT, rest)
+        };
+
+        let meters = (number * factor).round();
+        if !(i16::MIN as f32..=i16::MAX as f32).contains(&meters) {
+            return Err(invalid());
+        }
+
+        match reference {
+            "AMSL" | "MSL" | "ALT" => Ok(VerticalLimit::Msl(meters as i16)),
+            "AGL" | "GND" | "SFC" | "ASFC" => Ok(VerticalLimit::Agl(meters as i16)),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Convert meters to feet for display
+///
+/// Rounds to the nearest 10 ft if that still converts back to the same meter
+/// value (e.g. 457 m is shown as 1500 ft instead of 1499 ft), and to the
+/// nearest foot otherwise, so that parsing the output is lossless.
+fn to_feet(meters: i16) -> i32 {
+    let feet = meters as f32 / METERS_PER_FOOT;
+
+    let rounded = (feet / 10.).round() * 10.;
+    if (rounded * METERS_PER_FOOT).round() == meters as f32 {
+        rounded as i32
+    } else {
+        feet.round() as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::units::STANDARD_PRESSURE;
+    use claims::{assert_lt, assert_matches, assert_none, assert_ok_eq, assert_some};
+
+    #[test]
+    fn from_raw() {
+        let cases = [
+            ((0, AltStyle::AboveGroundLevel), VerticalLimit::Surface),
+            ((300, AltStyle::AboveGroundLevel), VerticalLimit::Agl(300)),
+            ((457, AltStyle::MeanSeaLevel), VerticalLimit::Msl(457)),
+            (
+                (2896, AltStyle::FlightLevel),
+                VerticalLimit::FlightLevel(95),
+            ),
+            (
+                (5944, AltStyle::FlightLevel),
+                VerticalLimit::FlightLevel(195),
+            ),
+            ((i16::MAX, AltStyle::Unlimited), VerticalLimit::Unlimited),
+            ((0, AltStyle::Notam), VerticalLimit::Notam),
+        ];
+
+        for ((alt, style), expected) in cases {
+            assert_eq!(VerticalLimit::from_raw(alt, style), Some(expected));
+            assert_eq!(expected.to_raw(), (alt, style));
+        }
+
+        assert_none!(VerticalLimit::from_raw(1000, AltStyle::Unknown));
+    }
+
+    #[test]
+    fn flight_levels_survive_raw_round_trip() {
+        for level in 0..=999 {
+            let limit = VerticalLimit::FlightLevel(level);
+            let (alt, style) = limit.to_raw();
+            assert_eq!(VerticalLimit::from_raw(alt, style), Some(limit));
+        }
+    }
+
+    #[test]
+    fn to_msl() {
+        let qnh = STANDARD_PRESSURE;
+        assert_eq!(VerticalLimit::Msl(1500).to_msl(qnh, None), Some(1500.));
+        assert_eq!(VerticalLimit::Agl(300).to_msl(qnh, Some(450.)), Some(750.));
+        assert_eq!(VerticalLimit::Surface.to_msl(qnh, Some(450.)), Some(450.));
+        assert_eq!(
+            VerticalLimit::Unlimited.to_msl(qnh, None),
+            Some(f32::INFINITY)
+        );
+        assert_none!(VerticalLimit::Agl(300).to_msl(qnh, None));
+        assert_none!(VerticalLimit::Surface.to_msl(qnh, None));
+        assert_none!(VerticalLimit::Notam.to_msl(qnh, Some(450.)));
+
+        let fl95 = VerticalLimit::FlightLevel(95);
+        let standard = assert_some!(fl95.to_msl(qnh, None));
+        assert_lt!((standard - 2895.6).abs(), 0.5);
+
+        // A low QNH moves the flight level down
+        let low = assert_some!(fl95.to_msl(1000., None));
+        assert_lt!((standard - low - 110.).abs(), 10.);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(VerticalLimit::Surface.to_string(), "GND");
+        assert_eq!(VerticalLimit::Msl(457).to_string(), "1500 ft AMSL");
+        assert_eq!(VerticalLimit::Msl(458).to_string(), "1503 ft AMSL");
+        assert_eq!(VerticalLimit::Agl(305).to_string(), "1000 ft AGL");
+        assert_eq!(VerticalLimit::Msl(-30).to_string(), "-100 ft AMSL");
+        assert_eq!(VerticalLimit::FlightLevel(95).to_string(), "FL95");
+        assert_eq!(VerticalLimit::Unlimited.to_string(), "UNL");
+        assert_eq!(VerticalLimit::Notam.to_string(), "NOTAM");
+    }
+
+    #[test]
+    fn parse() {
+        let cases = [
+            ("GND", VerticalLimit::Surface),
+            ("sfc", VerticalLimit::Surface),
+            ("FL95", VerticalLimit::FlightLevel(95)),
+            ("FL 195", VerticalLimit::FlightLevel(195)),
+            ("1500 ft AMSL", VerticalLimit::Msl(457)),
+            ("1500ft MSL", VerticalLimit::Msl(457)),
+            ("1000 AGL", VerticalLimit::Agl(305)),
+            ("300 m AGL", VerticalLimit::Agl(300)),
+            ("2000m MSL", VerticalLimit::Msl(2000)),
+            ("2500F ALT", VerticalLimit::Msl(762)),
+            ("1000 GND", VerticalLimit::Agl(305)),
+            ("500ft ASFC", VerticalLimit::Agl(152)),
+            ("UNL", VerticalLimit::Unlimited),
+            ("NOTAM", VerticalLimit::Notam),
+        ];
+
+        for (input, expected) in cases {
+            assert_ok_eq!(input.parse::<VerticalLimit>(), expected, "{input}");
+        }
+
+        for input in [
+            "",
+            "FL",
+            "FL-5",
+            "1500 ft",
+            "1500 ft ABC",
+            "200000 ft AMSL",
+            "FL9999",
+        ] {
+            assert_matches!(
+                input.parse::<VerticalLimit>(),
+                Err(Error::InvalidVerticalLimit(_)),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for meters in (-500..=10000).chain([i16::MIN, i16::MAX]) {
+            for limit in [VerticalLimit::Msl(meters), VerticalLimit::Agl(meters)] {
+                assert_ok_eq!(limit.to_string().parse::<VerticalLimit>(), limit);
+            }
+        }
+    }
+}
@@ -49,10 +49,19 @@ impl ByteString {
     ///
     /// Decoded string (always succeeds with some valid string)
     pub fn decode(&self) -> Cow<'_, str> {
-        match str::from_utf8(&self.0) {
-            Ok(s) => s.into(),
-            Err(_) => encoding_rs::WINDOWS_1252.decode(&self.0).0,
-        }
+        decode(&self.0)
+    }
+}
+
+/// Decode raw string bytes from a CUB file
+///
+/// Same as `ByteString::decode()`, but for borrowed bytes, e.g. from
+/// `CubSlice`. Attempts UTF-8 decoding first, falling back to Windows-1252
+/// (CP1252) if UTF-8 fails.
+pub fn decode(bytes: &[u8]) -> Cow<'_, str> {
+    match str::from_utf8(bytes) {
+        Ok(s) => s.into(),
+        Err(_) => encoding_rs::WINDOWS_1252.decode(bytes).0,
     }
 }
 
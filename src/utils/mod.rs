@@ -1,4 +1,5 @@
 mod byte_string;
 pub(crate) mod io;
+pub mod units;
 
-pub use self::byte_string::ByteString;
+pub use self::byte_string::{ByteString, decode};
@@ -0,0 +1,124 @@
+//! Altitude unit conversions and the ICAO standard atmosphere
+//!
+//! All altitudes in this crate are stored in meters. These helpers make the
+//! conversions to feet and between pressure altitude and altitude above mean
+//! sea level explicit.
+
+/// Meters per foot
+pub const METERS_PER_FOOT: f32 = 0.3048;
+
+/// Standard sea level pressure of the ICAO standard atmosphere (in hPa)
+pub const STANDARD_PRESSURE: f32 = 1013.25;
+
+/// Standard sea level temperature (in K)
+const STANDARD_TEMPERATURE: f32 = 288.15;
+
+/// Temperature lapse rate in the troposphere (in K/m)
+const LAPSE_RATE: f32 = 0.0065;
+
+/// Exponent of the barometric formula (`g * M / (R * L)`)
+const BAROMETRIC_EXPONENT: f32 = 5.255_88;
+
+/// Convert feet to meters
+pub fn feet_to_meters(feet: f32) -> f32 {
+    feet * METERS_PER_FOOT
+}
+
+/// Convert meters to feet
+pub fn meters_to_feet(meters: f32) -> f32 {
+    meters / METERS_PER_FOOT
+}
+
+/// Convert a flight level to pressure altitude in meters
+pub fn flight_level_to_meters(level: u16) -> f32 {
+    feet_to_meters(level as f32 * 100.)
+}
+
+/// Pressure (in hPa) at the given pressure altitude (in meters)
+fn pressure_at(pressure_altitude: f32) -> f32 {
+    STANDARD_PRESSURE
+        * (1. - LAPSE_RATE * pressure_altitude / STANDARD_TEMPERATURE).powf(BAROMETRIC_EXPONENT)
+}
+
+/// Altitude (in meters) at which the given pressure is reached, for an
+/// altimeter set to `qnh` (in hPa)
+fn altitude_at(pressure: f32, qnh: f32) -> f32 {
+    STANDARD_TEMPERATURE / LAPSE_RATE * (1. - (pressure / qnh).powf(1. / BAROMETRIC_EXPONENT))
+}
+
+/// Convert a pressure altitude (e.g. a flight level) to altitude above mean
+/// sea level
+///
+/// Uses the ICAO standard atmosphere (valid up to the tropopause at 11 km),
+/// i.e. only the sea level pressure `qnh` (in hPa) deviates from the
+/// standard, while the temperature is assumed to be standard.
+///
+/// ```
+/// use seeyou_cub::utils::units::{flight_level_to_meters, pressure_altitude_to_msl};
+///
+/// // FL50 at QNH 1013.25 hPa is 5000 ft MSL
+/// let msl = pressure_altitude_to_msl(flight_level_to_meters(50), 1013.25);
+/// assert!((msl - 1524.).abs() < 0.1);
+///
+/// // A higher QNH moves the flight level up (~8 m per hPa at this altitude)
+/// let msl = pressure_altitude_to_msl(flight_level_to_meters(50), 1023.25);
+/// assert!((msl - 1604.).abs() < 1.);
+/// ```
+pub fn pressure_altitude_to_msl(pressure_altitude: f32, qnh: f32) -> f32 {
+    altitude_at(pressure_at(pressure_altitude), qnh)
+}
+
+/// Convert an altitude above mean sea level to pressure altitude
+///
+/// Inverse of [`pressure_altitude_to_msl()`].
+pub fn msl_to_pressure_altitude(altitude: f32, qnh: f32) -> f32 {
+    let pressure =
+        qnh * (1. - LAPSE_RATE * altitude / STANDARD_TEMPERATURE).powf(BAROMETRIC_EXPONENT);
+    altitude_at(pressure, STANDARD_PRESSURE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::assert_lt;
+
+    #[test]
+    fn feet_and_meters() {
+        assert_lt!((feet_to_meters(1000.) - 304.8).abs(), 0.001);
+        assert_lt!((meters_to_feet(304.8) - 1000.).abs(), 0.001);
+        assert_lt!((flight_level_to_meters(95) - 2895.6).abs(), 0.001);
+    }
+
+    #[test]
+    fn standard_pressure_is_identity() {
+        for level in [0, 50, 95, 195, 350] {
+            let altitude = flight_level_to_meters(level);
+            assert_lt!(
+                (pressure_altitude_to_msl(altitude, STANDARD_PRESSURE) - altitude).abs(),
+                0.5
+            );
+        }
+    }
+
+    #[test]
+    fn qnh_shifts_flight_levels() {
+        // Rule of thumb: ~27 ft per hPa near sea level, decreasing with altitude
+        let altitude = flight_level_to_meters(65);
+        let high = pressure_altitude_to_msl(altitude, 1033.25) - altitude;
+        let low = pressure_altitude_to_msl(altitude, 993.25) - altitude;
+
+        assert_lt!((meters_to_feet(high) - 20. * 26.).abs(), 10.);
+        assert_lt!((meters_to_feet(-low) - 20. * 26.).abs(), 10.);
+    }
+
+    #[test]
+    fn msl_round_trip() {
+        for qnh in [980., 1013.25, 1040.] {
+            for altitude in [-100., 0., 1500., 5000., 10000.] {
+                let pressure_altitude = msl_to_pressure_altitude(altitude, qnh);
+                let msl = pressure_altitude_to_msl(pressure_altitude, qnh);
+                assert_lt!((msl - altitude).abs(), 0.5);
+            }
+        }
+    }
+}
@@ -0,0 +1,581 @@
+//! Airspace validation
+//!
+//! Checks airspaces for problems that make them unusable or that would be
+//! silently altered when written to a CUB file, and collects the results in a
+//! [`Report`]:
+//!
+//! ```no_run
+//! use seeyou_cub::CubReader;
+//! use seeyou_cub::validate::validate_file;
+//!
+//! let mut reader = CubReader::from_path("airspace.cub")?;
+//! let report = validate_file(&mut reader)?;
+//!
+//! print!("{report}");
+//! if report.has_errors() {
+//!     std::process::exit(1);
+//! }
+//! # Ok::<(), seeyou_cub::Error>(())
+//! ```
+//!
+//! See [`Rule`] for the list of checks.
+
+use crate::error::Result;
+use crate::{Airspace, AltStyle, BoundingBox, CubReader, Point};
+use std::fmt;
+use std::io::{Read, Seek};
+use std::ops::RangeInclusive;
+
+/// Maximum name length in bytes that `ItemData::write` stores without truncation
+const MAX_NAME_LENGTH: usize = 63;
+
+/// VHF communication airband in MHz
+///
+/// The navigation band below (108-117.975 MHz) is excluded, since airspace
+/// frequencies are always communication frequencies.
+const AIRBAND: RangeInclusive<f32> = 118.0..=137.0;
+
+/// Maximum difference between the stored bounding box and the bounding box of
+/// the points, in radians (about 60 meters)
+const BOUNDING_BOX_TOLERANCE: f32 = 1e-5;
+
+/// Validation rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    /// The polygon has fewer than 3 distinct points
+    TooFewPoints,
+    /// Two edges of the polygon cross each other
+    SelfIntersection,
+    /// The last point is not equal to the first point
+    UnclosedRing,
+    /// The closing point is repeated at the end of the ring
+    DuplicateClosingPoint,
+    /// `min_alt` is above `max_alt`
+    InvertedAltitudes,
+    /// `min_alt_style` or `max_alt_style` is `AltStyle::Unknown`
+    UnknownAltitudeStyle,
+    /// The name is longer than 63 bytes and would be truncated when written
+    NameTooLong,
+    /// A frequency is outside the VHF communication airband (118-137 MHz)
+    FrequencyOutOfRange,
+    /// `Airspace::bounding_box` does not match the points
+    BoundingBoxMismatch,
+}
+
+impl Rule {
+    /// Get the severity of issues reported by this rule
+    pub fn severity(&self) -> Severity {
+        match self {
+            Rule::TooFewPoints
+            | Rule::SelfIntersection
+            | Rule::InvertedAltitudes
+            | Rule::BoundingBoxMismatch => Severity::Error,
+            Rule::UnclosedRing
+            | Rule::DuplicateClosingPoint
+            | Rule::UnknownAltitudeStyle
+            | Rule::NameTooLong
+            | Rule::FrequencyOutOfRange => Severity::Warning,
+        }
+    }
+}
+
+/// Severity of a validation issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The airspace is usable, but probably not as intended
+    Warning,
+    /// The airspace is broken
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// Single problem found in an airspace
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    pub rule: Rule,
+    /// Human-readable description of the problem
+    pub message: String,
+}
+
+impl Issue {
+    fn new(rule: Rule, message: impl Into<String>) -> Self {
+        Self {
+            rule,
+            message: message.into(),
+        }
+    }
+
+    /// Get the severity of the issue
+    pub fn severity(&self) -> Severity {
+        self.rule.severity()
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} ({:?})", self.severity(), self.message, self.rule)
+    }
+}
+
+/// Issues found in a single airspace of a file
+#[derive(Debug, Clone, PartialEq)]
+pub struct AirspaceReport {
+    /// Index of the airspace in the file
+    pub index: usize,
+    pub name: String,
+    pub issues: Vec<Issue>,
+}
+
+/// Validation results for multiple airspaces
+///
+/// Only airspaces with at least one issue are included.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Report {
+    /// Number of validated airspaces
+    pub checked: usize,
+    pub airspaces: Vec<AirspaceReport>,
+}
+
+impl Report {
+    /// Check if no issues were found
+    pub fn is_empty(&self) -> bool {
+        self.airspaces.is_empty()
+    }
+
+    /// Check if any issue has `Severity::Error`
+    pub fn has_errors(&self) -> bool {
+        self.issues()
+            .any(|(_, issue)| issue.severity() == Severity::Error)
+    }
+
+    /// Iterate over all issues together with the index of their airspace
+    pub fn issues(&self) -> impl Iterator<Item = (usize, &Issue)> {
+        self.airspaces
+            .iter()
+            .flat_map(|report| report.issues.iter().map(|issue| (report.index, issue)))
+    }
+
+    /// Count the issues reported by a rule
+    pub fn count(&self, rule: Rule) -> usize {
+        self.issues()
+            .filter(|(_, issue)| issue.rule == rule)
+            .count()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for report in &self.airspaces {
+            for issue in &report.issues {
+                writeln!(f, "airspace {} ({}): {issue}", report.index, report.name)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Validate a single airspace
+///
+/// Returns all issues found, or an empty `Vec` if the airspace is valid.
+pub fn validate_airspace(airspace: &Airspace) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    check_geometry(airspace, &mut issues);
+    check_altitudes(airspace, &mut issues);
+    check_name(airspace, &mut issues);
+    check_frequencies(airspace, &mut issues);
+    check_bounding_box(airspace, &mut issues);
+
+    issues
+}
+
+/// Validate multiple airspaces
+pub fn validate_airspaces<'a, I: IntoIterator<Item = &'a Airspace>>(airspaces: I) -> Report {
+    let mut report = Report::default();
+    for airspace in airspaces {
+        report.add(airspace);
+    }
+    report
+}
+
+/// Validate all airspaces of a CUB file
+///
+/// Returns the first error encountered while decoding the airspaces.
+pub fn validate_file<R: Read + Seek>(reader: &mut CubReader<R>) -> Result<Report> {
+    let mut report = Report::default();
+    for airspace in reader.read_airspaces() {
+        report.add(&airspace?);
+    }
+    Ok(report)
+}
+
+impl Report {
+    fn add(&mut self, airspace: &Airspace) {
+        let index = self.checked;
+        self.checked += 1;
+
+        let issues = validate_airspace(airspace);
+        if !issues.is_empty() {
+            self.airspaces.push(AirspaceReport {
+                index,
+                name: airspace.name.clone(),
+                issues,
+            });
+        }
+    }
+}
+
+fn check_geometry(airspace: &Airspace, issues: &mut Vec<Issue>) {
+    let points = &airspace.points;
+
+    // Count trailing copies of the first point
+    let closing = match points.first() {
+        Some(first) if points.len() > 1 => points[1..]
+            .iter()
+            .rev()
+            .take_while(|point| *point == first)
+            .count(),
+        _ => 0,
+    };
+
+    // Distinct corners of the ring, without the closing point(s)
+    let ring = &points[..points.len() - closing];
+    if ring.len() < 3 {
+        issues.push(Issue::new(
+            Rule::TooFewPoints,
+            format!("polygon has only {} distinct points", ring.len()),
+        ));
+        return;
+    }
+
+    match closing {
+        0 => issues.push(Issue::new(
+            Rule::UnclosedRing,
+            "last point is not equal to the first point",
+        )),
+        1 => {}
+        n => issues.push(Issue::new(
+            Rule::DuplicateClosingPoint,
+            format!("closing point is repeated {n} times"),
+        )),
+    }
+
+    if let Some((a, b)) = find_self_intersection(ring) {
+        issues.push(Issue::new(
+            Rule::SelfIntersection,
+            format!("edges starting at points {a} and {b} cross each other"),
+        ));
+    }
+}
+
+/// Find two crossing edges of the (implicitly closed) ring
+///
+/// Coordinates are treated as planar, which is accurate enough for the size of
+/// typical airspaces. Edges that only touch are not reported.
+fn find_self_intersection(ring: &[Point]) -> Option<(usize, usize)> {
+    let n = ring.len();
+    let edge = |i: usize| (ring[i], ring[(i + 1) % n]);
+
+    for i in 0..n {
+        let (a1, a2) = edge(i);
+        // Skip the neighboring edges, they always share a point
+        for j in (i + 2)..n {
+            if i == 0 && j == n - 1 {
+                continue;
+            }
+
+            let (b1, b2) = edge(j);
+            if segments_cross(a1, a2, b1, b2) {
+                return Some((i, j));
+            }
+        }
+    }
+
+    None
+}
+
+/// Check if segment `a1`-`a2` properly crosses segment `b1`-`b2`
+fn segments_cross(a1: Point, a2: Point, b1: Point, b2: Point) -> bool {
+    let d1 = orientation(b1, b2, a1);
+    let d2 = orientation(b1, b2, a2);
+    let d3 = orientation(a1, a2, b1);
+    let d4 = orientation(a1, a2, b2);
+
+    d1 * d2 < 0. && d3 * d4 < 0.
+}
+
+/// Signed area of the triangle `a`, `b`, `c` (positive if counterclockwise)
+fn orientation(a: Point, b: Point, c: Point) -> f64 {
+    let (ax, ay) = (a.lon as f64, a.lat as f64);
+    let (bx, by) = (b.lon as f64, b.lat as f64);
+    let (cx, cy) = (c.lon as f64, c.lat as f64);
+
+    (bx - ax) * (cy - ay) - (by - ay) * (cx - ax)
+}
+
+fn check_altitudes(airspace: &Airspace, issues: &mut Vec<Issue>) {
+    // Altitudes with different references cannot be compared
+    if airspace.min_alt_style == airspace.max_alt_style && airspace.min_alt > airspace.max_alt {
+        issues.push(Issue::new(
+            Rule::InvertedAltitudes,
+            format!(
+                "lower limit {} m is above upper limit {} m",
+                airspace.min_alt, airspace.max_alt
+            ),
+        ));
+    }
+
+    for (limit, style) in [
+        ("lower", airspace.min_alt_style),
+        ("upper", airspace.max_alt_style),
+    ] {
+        if style == AltStyle::Unknown {
+            issues.push(Issue::new(
+                Rule::UnknownAltitudeStyle,
+                format!("{limit} limit has an unknown altitude reference"),
+            ));
+        }
+    }
+}
+
+fn check_name(airspace: &Airspace, issues: &mut Vec<Issue>) {
+    let length = airspace.name.len();
+    if length > MAX_NAME_LENGTH {
+        issues.push(Issue::new(
+            Rule::NameTooLong,
+            format!("name is {length} bytes long and will be truncated to {MAX_NAME_LENGTH} bytes"),
+        ));
+    }
+}
+
+fn check_frequencies(airspace: &Airspace, issues: &mut Vec<Issue>) {
+    for (kind, frequency) in [
+        ("primary", airspace.frequency),
+        ("secondary", airspace.secondary_frequency),
+    ] {
+        if let Some(frequency) = frequency
+            && !AIRBAND.contains(&frequency)
+        {
+            issues.push(Issue::new(
+                Rule::FrequencyOutOfRange,
+                format!(
+                    "{kind} frequency {frequency:.3} MHz is outside the VHF communication airband"
+                ),
+            ));
+        }
+    }
+}
+
+fn check_bounding_box(airspace: &Airspace, issues: &mut Vec<Issue>) {
+    let (Some(stored), Some(actual)) = (
+        airspace.bounding_box,
+        BoundingBox::from_points(&airspace.points),
+    ) else {
+        return;
+    };
+
+    let max_difference = [
+        stored.left - actual.left,
+        stored.top - actual.top,
+        stored.right - actual.right,
+        stored.bottom - actual.bottom,
+    ]
+    .into_iter()
+    .map(f32::abs)
+    .fold(0., f32::max);
+
+    if max_difference > BOUNDING_BOX_TOLERANCE {
+        issues.push(Issue::new(
+            Rule::BoundingBoxMismatch,
+            format!(
+                "bounding box differs from the points by {:.0} m",
+                max_difference * crate::types::EARTH_RADIUS
+            ),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CubStyle;
+    use insta::assert_snapshot;
+
+    fn square() -> Airspace {
+        let deg = f32::to_radians;
+        Airspace {
+            name: "Square".to_string(),
+            style: CubStyle::DangerArea,
+            min_alt: 0,
+            max_alt: 1000,
+            min_alt_style: AltStyle::AboveGroundLevel,
+            max_alt_style: AltStyle::MeanSeaLevel,
+            frequency: Some(123.5),
+            points: vec![
+                Point::lat_lon(deg(45.), deg(5.)),
+                Point::lat_lon(deg(45.), deg(6.)),
+                Point::lat_lon(deg(46.), deg(6.)),
+                Point::lat_lon(deg(46.), deg(5.)),
+                Point::lat_lon(deg(45.), deg(5.)),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn rules(airspace: &Airspace) -> Vec<Rule> {
+        validate_airspace(airspace)
+            .into_iter()
+            .map(|issue| issue.rule)
+            .collect()
+    }
+
+    #[test]
+    fn valid_airspace() {
+        assert_eq!(validate_airspace(&square()), vec![]);
+    }
+
+    #[test]
+    fn too_few_points() {
+        let mut airspace = square();
+        airspace.points.truncate(2);
+        assert_eq!(rules(&airspace), vec![Rule::TooFewPoints]);
+
+        // Closing points are not counted
+        let mut airspace = square();
+        airspace.points = vec![airspace.points[0], airspace.points[1], airspace.points[0]];
+        assert_eq!(rules(&airspace), vec![Rule::TooFewPoints]);
+    }
+
+    #[test]
+    fn ring_closure() {
+        let mut airspace = square();
+        airspace.points.pop();
+        assert_eq!(rules(&airspace), vec![Rule::UnclosedRing]);
+
+        let mut airspace = square();
+        airspace.points.push(airspace.points[0]);
+        assert_eq!(rules(&airspace), vec![Rule::DuplicateClosingPoint]);
+    }
+
+    #[test]
+    fn self_intersection() {
+        // Bow tie
+        let mut airspace = square();
+        airspace.points.swap(1, 2);
+        assert_eq!(rules(&airspace), vec![Rule::SelfIntersection]);
+
+        // Also detected across the implicit closing edge
+        airspace.points.pop();
+        assert_eq!(
+            rules(&airspace),
+            vec![Rule::UnclosedRing, Rule::SelfIntersection]
+        );
+    }
+
+    #[test]
+    fn altitudes() {
+        let mut airspace = square();
+        airspace.min_alt_style = AltStyle::MeanSeaLevel;
+        airspace.min_alt = 2000;
+        assert_eq!(rules(&airspace), vec![Rule::InvertedAltitudes]);
+
+        let mut airspace = square();
+        airspace.min_alt_style = AltStyle::Unknown;
+        airspace.max_alt_style = AltStyle::Unknown;
+        assert_eq!(
+            rules(&airspace),
+            vec![Rule::UnknownAltitudeStyle, Rule::UnknownAltitudeStyle]
+        );
+    }
+
+    #[test]
+    fn name_too_long() {
+        let mut airspace = square();
+        airspace.name = "A".repeat(63);
+        assert_eq!(rules(&airspace), vec![]);
+
+        // Length is measured in bytes, not characters
+        airspace.name = "É".repeat(32);
+        assert_eq!(rules(&airspace), vec![Rule::NameTooLong]);
+    }
+
+    #[test]
+    fn frequency_out_of_range() {
+        let mut airspace = square();
+        airspace.frequency = Some(118.0);
+        airspace.secondary_frequency = Some(137.0);
+        assert_eq!(rules(&airspace), vec![]);
+
+        // VOR/ILS navigation frequency
+        airspace.frequency = Some(108.0);
+        assert_eq!(rules(&airspace), vec![Rule::FrequencyOutOfRange]);
+
+        airspace.frequency = Some(1234.5);
+        airspace.secondary_frequency = Some(0.0);
+        assert_eq!(
+            rules(&airspace),
+            vec![Rule::FrequencyOutOfRange, Rule::FrequencyOutOfRange]
+        );
+    }
+
+    #[test]
+    fn bounding_box_mismatch() {
+        let mut airspace = square();
+        airspace.bounding_box = BoundingBox::from_points(&airspace.points);
+        assert_eq!(rules(&airspace), vec![]);
+
+        airspace.bounding_box.as_mut().unwrap().top += 0.001;
+        assert_eq!(rules(&airspace), vec![Rule::BoundingBoxMismatch]);
+    }
+
+    #[test]
+    fn report_display() {
+        let mut broken = square();
+        broken.name = "Broken".to_string();
+        broken.points.pop();
+        broken.min_alt_style = AltStyle::MeanSeaLevel;
+        broken.min_alt = 2000;
+
+        let report = validate_airspaces(&[square(), broken]);
+        assert_eq!(report.checked, 2);
+        assert!(report.has_errors());
+        assert_eq!(report.count(Rule::UnclosedRing), 1);
+        assert_snapshot!(report, @r"
+        airspace 1 (Broken): warning: last point is not equal to the first point (UnclosedRing)
+        airspace 1 (Broken): error: lower limit 2000 m is above upper limit 1000 m (InvertedAltitudes)
+        ");
+    }
+
+    #[test]
+    fn validate_france_fixture() {
+        let mut reader = CubReader::from_path("tests/fixtures/france_2024.07.02.cub")
+            .expect("Failed to open fixture");
+
+        let report = validate_file(&mut reader).expect("Failed to validate");
+        assert_eq!(report.checked, 1368);
+
+        let summary: Vec<_> = [
+            Rule::TooFewPoints,
+            Rule::SelfIntersection,
+            Rule::UnclosedRing,
+            Rule::DuplicateClosingPoint,
+            Rule::InvertedAltitudes,
+            Rule::UnknownAltitudeStyle,
+            Rule::NameTooLong,
+            Rule::FrequencyOutOfRange,
+            Rule::BoundingBoxMismatch,
+        ]
+        .into_iter()
+        .map(|rule| (rule, report.count(rule)))
+        .collect();
+        insta::assert_debug_snapshot!(summary);
+    }
+}
@@ -1,8 +1,8 @@
 use crate::error::Result;
-use crate::raw::{Header, Item, ItemData, PointOp};
+use crate::raw::{Header, Item, ItemData, NotamTime, PointOp};
 use crate::utils::ByteString;
-use crate::{Airspace, AltStyle, BoundingBox, ByteOrder, CubClass, CubStyle, DaysActive, Point};
-use std::io::Cursor;
+use crate::{Airspace, BoundingBox, ByteOrder, DaysActive, Point};
+use std::io::{self, BufWriter, Cursor, Read, Seek, SeekFrom, Write};
 
 /// Default coordinate scale factor
 ///
@@ -18,6 +18,9 @@ const DEFAULT_LO_LA_SCALE: f32 = 1.5723687e-7;
 /// coordinate scales, bounding boxes, and byte offsets are calculated automatically
 /// before writing the complete file.
 ///
+/// For datasets that should not be held in memory at once, see
+/// [`CubWriter::streaming()`].
+///
 /// # Design
 ///
 /// The writer uses a mutable builder pattern with `&mut self` methods for chaining:
@@ -94,6 +97,7 @@ pub struct CubWriter {
     airspaces: Vec<Airspace>,
     byte_order: ByteOrder,
     lo_la_scale: f32,
+    crc: bool,
 }
 
 impl CubWriter {
@@ -108,6 +112,7 @@ impl CubWriter {
             airspaces: Vec::new(),
             byte_order: ByteOrder::LE,
             lo_la_scale: DEFAULT_LO_LA_SCALE,
+            crc: false,
         }
     }
 
@@ -119,113 +124,77 @@ impl CubWriter {
     /// # Returns
     ///
     /// Ok(()) on success or an error if writing fails
-    pub fn write<W: std::io::Write + std::io::Seek>(&mut self, mut writer: W) -> Result<()> {
-        let max_pts = self.airspaces.iter().map(|a| a.points.len()).max();
+    pub fn write<W: Write + Seek>(&mut self, writer: W) -> Result<()> {
+        let mut stream = self.streaming(writer, Cursor::new(Vec::new()))?;
+        for airspace in self.airspaces.drain(..) {
+            stream.add_airspace(airspace)?;
+        }
+        stream.finish()?;
+
+        Ok(())
+    }
 
-        // Create header with known values (will update counts and offsets later)
-        let mut header = Header {
+    /// Start a streaming write with the current configuration
+    ///
+    /// Unlike `write()`, the returned [`StreamingCubWriter`] takes airspaces
+    /// one at a time and does not keep them in memory. Items are written to
+    /// `writer` directly, while the variable-length item data is written to
+    /// the `spill` buffer (e.g. a temporary file) and copied to `writer` when
+    /// the stream is finished.
+    ///
+    /// Airspaces previously added via `add_airspace()` are **not** written.
+    ///
+    /// ```no_run
+    /// use seeyou_cub::writer::CubWriter;
+    /// use seeyou_cub::Airspace;
+    /// use std::fs::File;
+    /// use std::io::BufWriter;
+    ///
+    /// # fn airspaces() -> Vec<Airspace> { vec![] }
+    /// let output = BufWriter::new(File::create("output.cub")?);
+    /// let spill = File::options()
+    ///     .read(true)
+    ///     .write(true)
+    ///     .create(true)
+    ///     .truncate(true)
+    ///     .open("output.cub.tmp")?;
+    ///
+    /// let mut stream = CubWriter::new("Streamed").streaming(output, spill)?;
+    /// for airspace in airspaces() {
+    ///     stream.add_airspace(airspace)?;
+    /// }
+    /// stream.finish()?;
+    /// # Ok::<(), seeyou_cub::Error>(())
+    /// ```
+    pub fn streaming<W, S>(&self, writer: W, spill: S) -> Result<StreamingCubWriter<W, S>>
+    where
+        W: Write + Seek,
+        S: Read + Write + Seek,
+    {
+        let header = Header {
             title: ByteString::from(self.title.as_bytes().to_vec()),
             allowed_serials: [0; 8],
             pc_byte_order: self.byte_order.as_pc_byte_order(),
+            crc32: 0,
             key: [0; 16],
             size_of_item: 43,
             size_of_point: 5,
-            hdr_items: self.airspaces.len() as i32,
-            max_pts: max_pts.unwrap_or(0) as i32,
+            hdr_items: 0, // Will be updated later
+            max_pts: 0,   // Will be updated later
             bounding_box: BoundingBox {
                 left: 0.0,
                 top: 0.0,
                 right: 0.0,
                 bottom: 0.0,
             }, // Will be updated later
-            max_width: 0.0,  // Will be updated later
+            max_width: 0.0, // Will be updated later
             max_height: 0.0, // Will be updated later
             lo_la_scale: self.lo_la_scale,
             data_offset: 0, // Will be updated later
+            alignment: 0,
         };
 
-        let mut global_bbox: Option<BoundingBox> = None;
-        let mut items_buffer = Cursor::new(Vec::new());
-        let mut item_data_buffer = Cursor::new(Vec::new());
-
-        for airspace in self.airspaces.drain(..) {
-            // Calculate bbox if missing
-            let bbox = airspace
-                .bounding_box
-                .or_else(|| BoundingBox::from_points(&airspace.points))
-                .unwrap_or_else(|| BoundingBox::from(Point::lat_lon(0., 0.)));
-
-            // Accumulate into global bbox
-            match global_bbox {
-                None => global_bbox = Some(bbox),
-                Some(ref mut global) => global.merge(bbox),
-            }
-
-            // Convert points to PointOps
-            let point_ops =
-                PointOp::from_points(&airspace.points, self.lo_la_scale, bbox.left, bbox.bottom)?;
-
-            // Record current data offset (for `Item::points_offset` field)
-            let data_offset = item_data_buffer.position() as i32;
-
-            // Create `ItemData` and write to data buffer
-            let name = if airspace.name.is_empty() {
-                None
-            } else {
-                Some(ByteString::from(airspace.name))
-            };
-
-            let item_data = ItemData {
-                point_ops,
-                name,
-                frequency: airspace.frequency.map(|f| (f * 1000.) as u32),
-                frequency_name: airspace.frequency_name.map(ByteString::from),
-                icao_code: airspace.icao_code.map(ByteString::from),
-                secondary_frequency: airspace.secondary_frequency.map(|f| (f * 1000.) as u32),
-                exception_rules: airspace.exception_rules.map(ByteString::from),
-                notam_remarks: airspace.notam_remarks.map(ByteString::from),
-                notam_id: airspace.notam_id.map(ByteString::from),
-                notam_insert_time: airspace.notam_insert_time,
-            };
-            item_data.write(&mut item_data_buffer, &header)?;
-
-            // Create and write Item
-            let item = Item {
-                bounding_box: bbox,
-                type_byte: encode_type_byte(airspace.style, airspace.class),
-                alt_style_byte: encode_alt_style_byte(
-                    airspace.min_alt_style,
-                    airspace.max_alt_style,
-                ),
-                min_alt: airspace.min_alt,
-                max_alt: airspace.max_alt,
-                points_offset: data_offset,
-                extra_data: airspace.extra_data,
-                active_time: encode_active_time(
-                    airspace.start_date.as_ref(),
-                    airspace.end_date.as_ref(),
-                    &airspace.days_active,
-                ),
-                extended_type_byte: airspace.extended_type.map(|t| t.as_byte()).unwrap_or(0),
-            };
-            item.write(&mut items_buffer, &header)?;
-        }
-
-        // Update header with calculated values
-        let items_size = items_buffer.position() as i32;
-        header.data_offset = crate::raw::HEADER_SIZE as i32 + items_size;
-
-        if let Some(bbox) = global_bbox {
-            header.bounding_box = bbox;
-        }
-        header.max_width = header.bounding_box.right - header.bounding_box.left;
-        header.max_height = header.bounding_box.top - header.bounding_box.bottom;
-
-        header.write(&mut writer)?;
-        writer.write_all(&items_buffer.into_inner())?;
-        writer.write_all(&item_data_buffer.into_inner())?;
-
-        Ok(())
+        StreamingCubWriter::new(writer, spill, header, self.crc)
     }
 
     /// Add a single airspace to the writer
@@ -262,6 +231,17 @@ impl CubWriter {
         self
     }
 
+    /// Enable or disable the header CRC32
+    ///
+    /// If enabled, a CRC32 over the items and item data is stored in the
+    /// header, which can be checked with `CubReader::verify_crc()`. Disabled
+    /// by default, in which case `0` is stored.
+    /// Returns `&mut self` to allow method chaining.
+    pub fn with_crc(&mut self, enabled: bool) -> &mut Self {
+        self.crc = enabled;
+        self
+    }
+
     /// Write CUB file to a file path
     ///
     /// Convenience wrapper around `write()` that creates a file at the given path.
@@ -271,60 +251,238 @@ impl CubWriter {
     /// Ok(()) on success or an error if file creation or writing fails
     pub fn write_to_path<P: AsRef<std::path::Path>>(&mut self, path: P) -> Result<()> {
         let file = std::fs::File::create(path)?;
-        self.write(file)
+        self.write(BufWriter::new(file))
     }
 }
 
-// Helper functions for encoding bit-packed fields
-fn encode_type_byte(style: CubStyle, class: CubClass) -> u8 {
-    let style_nibble = style.as_nibble();
-    let class_nibble = class.as_nibble();
-    (style_nibble << 4) | class_nibble
+/// Streaming CUB file writer
+///
+/// Created by [`CubWriter::streaming()`]. Airspaces are encoded as soon as
+/// they are added, so memory usage does not grow with the number of airspaces:
+///
+/// - A placeholder header is written when the stream is created.
+/// - Each `Item` is written to the output right after the previous one.
+/// - Each `ItemData` block is appended to the spill buffer.
+/// - `finish()` copies the spill buffer to the output and then seeks back to
+///   patch the header with the final item count, `max_pts`, bounding box,
+///   data offset and (if enabled) CRC32.
+///
+/// If `finish()` is not called, the output contains an invalid header.
+pub struct StreamingCubWriter<W, S> {
+    writer: W,
+    spill: S,
+    header: Header,
+    /// Position of the header in `writer`
+    header_position: u64,
+    /// Position of the first `ItemData` block in `spill`
+    spill_start: u64,
+    global_bbox: Option<BoundingBox>,
+    /// CRC32 of everything written after the header, if enabled
+    crc: Option<crc32fast::Hasher>,
+}
+
+impl<W: Write + Seek, S: Read + Write + Seek> StreamingCubWriter<W, S> {
+    fn new(mut writer: W, mut spill: S, header: Header, crc: bool) -> Result<Self> {
+        let header_position = writer.stream_position()?;
+        let spill_start = spill.stream_position()?;
+
+        // Reserve space for the header, it is patched in `finish()`
+        header.write(&mut writer)?;
+
+        Ok(Self {
+            writer,
+            spill,
+            header,
+            header_position,
+            spill_start,
+            global_bbox: None,
+            crc: crc.then(crc32fast::Hasher::new),
+        })
+    }
+
+    /// Encode and write a single airspace
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) on success or an error if encoding or writing fails
+    pub fn add_airspace(&mut self, airspace: Airspace) -> Result<()> {
+        let active_time = encode_active_time(
+            airspace.start_date.as_ref(),
+            airspace.end_date.as_ref(),
+            &airspace.days_active,
+        )?;
+        let notam_insert_time = airspace
+            .notam_insert_time
+            .map(|time| NotamTime::encode(time).map(NotamTime::to_raw))
+            .transpose()?;
+
+        let lo_la_scale = self.header.lo_la_scale;
+
+        // Calculate bbox if missing
+        let bbox = airspace
+            .bounding_box
+            .or_else(|| BoundingBox::from_points(&airspace.points))
+            .unwrap_or_else(|| BoundingBox::from(Point::lat_lon(0., 0.)));
+
+        // Accumulate into global bbox
+        match self.global_bbox {
+            None => self.global_bbox = Some(bbox),
+            Some(ref mut global) => global.merge(bbox),
+        }
+
+        let max_pts = self.header.max_pts.max(airspace.points.len() as i32);
+
+        // Convert points to PointOps
+        let point_ops =
+            PointOp::from_points(&airspace.points, lo_la_scale, bbox.left, bbox.bottom)?;
+
+        // Record current data offset (for `Item::points_offset` field)
+        let data_offset = (self.spill.stream_position()? - self.spill_start) as i32;
+
+        // Create `ItemData` and write to spill buffer
+        let name = if airspace.name.is_empty() {
+            None
+        } else {
+            Some(ByteString::from(airspace.name))
+        };
+
+        let item_data = ItemData {
+            point_ops,
+            name,
+            frequency: airspace.frequency.map(|f| (f * 1000.) as u32),
+            frequency_name: airspace.frequency_name.map(ByteString::from),
+            icao_code: airspace.icao_code.map(ByteString::from),
+            secondary_frequency: airspace.secondary_frequency.map(|f| (f * 1000.) as u32),
+            exception_rules: airspace.exception_rules.map(ByteString::from),
+            notam_remarks: airspace.notam_remarks.map(ByteString::from),
+            notam_id: airspace.notam_id.map(ByteString::from),
+            notam_insert_time,
+        };
+        item_data.write(&mut self.spill, &self.header)?;
+
+        // Create and write Item
+        let mut item = Item {
+            bounding_box: bbox,
+            type_byte: 0,
+            alt_style_byte: 0,
+            min_alt: airspace.min_alt,
+            max_alt: airspace.max_alt,
+            points_offset: data_offset,
+            extra_data: airspace.extra_data,
+            active_time,
+            extended_type_byte: 0,
+        };
+        item.set_style(airspace.style);
+        item.set_class(airspace.class);
+        item.set_alt_styles(airspace.min_alt_style, airspace.max_alt_style);
+        item.set_extended_type(airspace.extended_type);
+        let mut output = HashingWriter {
+            writer: &mut self.writer,
+            crc: &mut self.crc,
+        };
+        item.write(&mut output, &self.header)?;
+
+        self.header.hdr_items += 1;
+        self.header.max_pts = max_pts;
+
+        Ok(())
+    }
+
+    /// Copy the item data to the output and patch the header
+    ///
+    /// # Returns
+    ///
+    /// The underlying writer, positioned at the end of the file
+    pub fn finish(mut self) -> Result<W> {
+        let header = &mut self.header;
+        let items_size = header.hdr_items * header.size_of_item;
+        header.data_offset = crate::raw::HEADER_SIZE as i32 + items_size;
+
+        if let Some(bbox) = self.global_bbox {
+            header.bounding_box = bbox;
+        }
+        header.max_width = header.bounding_box.right - header.bounding_box.left;
+        header.max_height = header.bounding_box.top - header.bounding_box.bottom;
+
+        // Append item data
+        let spill_end = self.spill.stream_position()?;
+        self.spill.seek(SeekFrom::Start(self.spill_start))?;
+        let mut item_data = (&mut self.spill).take(spill_end - self.spill_start);
+        io::copy(
+            &mut item_data,
+            &mut HashingWriter {
+                writer: &mut self.writer,
+                crc: &mut self.crc,
+            },
+        )?;
+
+        if let Some(crc) = self.crc.take() {
+            self.header.set_crc32(Some(crc.finalize()));
+        }
+
+        // Patch header
+        let end = self.writer.stream_position()?;
+        self.writer.seek(SeekFrom::Start(self.header_position))?;
+        self.header.write(&mut self.writer)?;
+        self.writer.seek(SeekFrom::Start(end))?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
 }
 
-fn encode_alt_style_byte(min: AltStyle, max: AltStyle) -> u8 {
-    let min_nibble = min.as_nibble();
-    let max_nibble = max.as_nibble();
-    (min_nibble << 4) | max_nibble
+/// Writer adapter that feeds all written bytes into an optional CRC32 hasher
+struct HashingWriter<'a, W> {
+    writer: &'a mut W,
+    crc: &'a mut Option<crc32fast::Hasher>,
 }
 
-fn encode_notam_time(dt: &crate::DateTime) -> u32 {
-    let year = dt.year - 2000;
-    let month = (dt.month - 1) as u32;
-    let day = (dt.day - 1) as u32;
-    let hour = dt.hour as u32;
-    let minute = dt.minute as u32;
+impl<W: Write> Write for HashingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.writer.write(buf)?;
+        if let Some(crc) = self.crc {
+            crc.update(&buf[..written]);
+        }
+        Ok(written)
+    }
 
-    minute + 60 * (hour + 24 * (day + 31 * (month + 12 * year)))
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
 }
 
+// Helper functions for encoding bit-packed fields
 fn encode_active_time(
     start_date: Option<&crate::DateTime>,
     end_date: Option<&crate::DateTime>,
     days: &DaysActive,
-) -> u64 {
+) -> Result<u64> {
     let days_bits = (days.as_bits() & 0xFFF) << 52;
 
     let start_bits = if let Some(dt) = start_date {
-        (encode_notam_time(dt) as u64) << 26
+        (NotamTime::encode(*dt)?.to_raw() as u64) << 26
     } else {
         0
     };
 
     let end_bits = if let Some(dt) = end_date {
-        encode_notam_time(dt) as u64
+        NotamTime::encode(*dt)?.to_raw() as u64
     } else {
-        0x3FFFFFF // Max value indicates no end date
+        NotamTime::MAX_ACTIVE_TIME as u64 // Max value indicates no end date
     };
 
-    days_bits | start_bits | end_bits
+    Ok(days_bits | start_bits | end_bits)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{AltStyle, CubClass, CubReader, CubStyle, DaysActive, Point};
-    use claims::{assert_lt, assert_some};
+    use crate::Error;
+    use crate::{
+        AltStyle, CubClass, CubReader, CubStyle, DaysActive, ExtendedType, NotamCodes, NotamInfo,
+        NotamScope, NotamTraffic, NotamType, Point,
+    };
+    use claims::{assert_lt, assert_matches, assert_ok_eq, assert_some};
     use insta::assert_debug_snapshot;
     use std::io::Cursor;
 
@@ -552,6 +710,19 @@ mod tests {
             .enumerate()
         {
             assert_eq!(original.name, read_back.name, "Airspace {i} name mismatch");
+            assert_eq!(
+                original.style, read_back.style,
+                "Airspace {i} style mismatch"
+            );
+            assert_eq!(
+                original.class, read_back.class,
+                "Airspace {i} class mismatch"
+            );
+            assert_eq!(
+                (original.min_alt_style, original.max_alt_style),
+                (read_back.min_alt_style, read_back.max_alt_style),
+                "Airspace {i} altitude style mismatch",
+            );
             assert_eq!(
                 original.points.len(),
                 read_back.points.len(),
@@ -565,4 +736,276 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn round_trip_all_styles() {
+        let airspaces: Vec<_> = CubStyle::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, &style)| Airspace {
+                style,
+                class: CubClass::ALL[i % CubClass::ALL.len()],
+                min_alt_style: AltStyle::ALL[i % AltStyle::ALL.len()],
+                max_alt_style: AltStyle::ALL[(i + 1) % AltStyle::ALL.len()],
+                extended_type: ExtendedType::ALL.get(i).copied(),
+                points: vec![Point::lat_lon(0.8, 0.4), Point::lat_lon(0.81, 0.41)],
+                name: format!("{style:?}"),
+                ..Default::default()
+            })
+            .collect();
+
+        let mut cursor = Cursor::new(Vec::new());
+        CubWriter::new("Styles")
+            .add_airspaces(airspaces.clone())
+            .write(&mut cursor)
+            .expect("Failed to write");
+
+        cursor.set_position(0);
+        let read_back: Vec<_> = CubReader::new(&mut cursor)
+            .expect("Failed to read")
+            .read_airspaces()
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+
+        assert_eq!(read_back.len(), 31);
+        for (original, read_back) in airspaces.iter().zip(&read_back) {
+            assert_eq!(read_back.style, original.style);
+            assert_eq!(read_back.class, original.class);
+            assert_eq!(read_back.min_alt_style, original.min_alt_style);
+            assert_eq!(read_back.max_alt_style, original.max_alt_style);
+            assert_eq!(read_back.extended_type, original.extended_type);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        let date = |year, month, day| crate::DateTime {
+            day,
+            month,
+            year,
+            hour: 0,
+            minute: 0,
+        };
+
+        for (start_date, end_date) in [
+            (Some(date(1999, 12, 31)), None),
+            (None, Some(date(2024, 2, 30))),
+        ] {
+            let airspace = Airspace {
+                points: vec![Point::lat_lon(0.8, 0.4), Point::lat_lon(0.81, 0.41)],
+                start_date,
+                end_date,
+                ..Default::default()
+            };
+
+            let mut cursor = Cursor::new(Vec::new());
+            let result = CubWriter::new("Dates")
+                .add_airspace(airspace)
+                .write(&mut cursor);
+            assert_matches!(result, Err(Error::InvalidDateTime(_)));
+        }
+    }
+
+    #[test]
+    fn notam_insert_time_round_trip() {
+        let inserted = crate::DateTime::new(2024, 7, 2, 6, 45).unwrap();
+        let airspace = Airspace {
+            points: vec![Point::lat_lon(0.8, 0.4), Point::lat_lon(0.81, 0.41)],
+            name: "NOTAM".to_string(),
+            notam_insert_time: Some(inserted),
+            ..Default::default()
+        };
+
+        let mut cursor = Cursor::new(Vec::new());
+        CubWriter::new("NOTAM")
+            .add_airspace(airspace)
+            .write(&mut cursor)
+            .expect("Failed to write");
+
+        cursor.set_position(0);
+        let mut reader = CubReader::new(&mut cursor).expect("Failed to read");
+        let read_back = assert_some!(reader.airspace(0)).expect("Failed to read airspace");
+        assert_eq!(read_back.notam_insert_time, Some(inserted));
+    }
+
+    #[test]
+    fn notam_info_round_trip() {
+        let notam_info = NotamInfo {
+            notam_type: NotamType::New,
+            codes: Some(NotamCodes {
+                subject: ('R', 'T'),
+                action: ('C', 'A'),
+            }),
+            traffic: NotamTraffic::Vfr,
+            scope: NotamScope::NavWarning,
+        };
+
+        let mut notam = Airspace {
+            name: "NOTAM".to_string(),
+            points: vec![Point::lat_lon(0.8, 0.4), Point::lat_lon(0.81, 0.41)],
+            extra_data: 0xFFFF_FFFF,
+            ..Default::default()
+        };
+        notam
+            .set_notam_info(Some(notam_info))
+            .expect("Failed to set NOTAM info");
+        assert_eq!(notam.notam_info(), Some(notam_info));
+
+        let airspaces = [
+            notam,
+            // `extra_data` without NOTAM data is written unchanged
+            Airspace {
+                name: "Raw".to_string(),
+                points: vec![Point::lat_lon(0.8, 0.4), Point::lat_lon(0.81, 0.41)],
+                extra_data: 0x8000_0001,
+                ..Default::default()
+            },
+        ];
+
+        let mut cursor = Cursor::new(Vec::new());
+        CubWriter::new("NOTAM")
+            .add_airspaces(airspaces)
+            .write(&mut cursor)
+            .expect("Failed to write");
+
+        cursor.set_position(0);
+        let read_back: Vec<_> = CubReader::new(&mut cursor)
+            .expect("Failed to read")
+            .read_airspaces()
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+
+        assert_eq!(read_back[0].notam_info(), Some(notam_info));
+        assert_ok_eq!(notam_info.to_extra_data(), read_back[0].extra_data);
+        assert!(read_back[0].has_notam_data());
+
+        assert_eq!(read_back[1].notam_info(), None);
+        assert_eq!(read_back[1].extra_data, 0x8000_0001);
+    }
+
+    #[test]
+    fn crc_round_trip() {
+        let airspaces: Vec<_> = CubReader::from_path("tests/fixtures/france_2024.07.02.cub")
+            .expect("Failed to open")
+            .read_airspaces()
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+
+        let mut cursor = Cursor::new(Vec::new());
+        CubWriter::new("CRC")
+            .with_crc(true)
+            .add_airspaces(airspaces)
+            .write(&mut cursor)
+            .expect("Failed to write");
+
+        let mut bytes = cursor.into_inner();
+        let mut reader = CubReader::new(Cursor::new(&bytes)).expect("Failed to read");
+        assert_ne!(reader.raw_header().crc32, 0);
+        assert_ok_eq!(reader.verify_crc(), true);
+
+        // Flip a bit in the item data of the last airspace
+        *bytes.last_mut().unwrap() ^= 0x01;
+        let mut reader = CubReader::new(Cursor::new(&bytes)).expect("Failed to read");
+        assert_matches!(reader.verify_crc(), Err(Error::CrcMismatch { .. }));
+
+        // A stored CRC of zero is still verified if the header flag is set
+        let mut header = reader.raw_header().clone();
+        header.set_crc32(Some(0));
+        header
+            .write(&mut &mut bytes[..])
+            .expect("Failed to write header");
+        let mut reader = CubReader::new(Cursor::new(&bytes)).expect("Failed to read");
+        assert_matches!(
+            reader.verify_crc(),
+            Err(Error::CrcMismatch { expected: 0, .. })
+        );
+    }
+
+    #[test]
+    fn crc_disabled_by_default() {
+        let mut reader =
+            CubReader::from_path("tests/fixtures/france_2024.07.02.cub").expect("Failed to open");
+        assert_ok_eq!(reader.verify_crc(), false);
+
+        let mut cursor = Cursor::new(Vec::new());
+        CubWriter::new("No CRC")
+            .write(&mut cursor)
+            .expect("Failed to write");
+
+        cursor.set_position(0);
+        let mut reader = CubReader::new(cursor).expect("Failed to read");
+        assert_eq!(reader.raw_header().crc32, 0);
+        assert_ok_eq!(reader.verify_crc(), false);
+    }
+
+    #[test]
+    fn streaming_matches_buffered_output() {
+        let airspaces: Vec<_> = CubReader::from_path("tests/fixtures/france_2024.07.02.cub")
+            .expect("Failed to open")
+            .read_airspaces()
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+
+        let mut buffered = Cursor::new(Vec::new());
+        CubWriter::new("Streaming Test")
+            .add_airspaces(airspaces.clone())
+            .write(&mut buffered)
+            .expect("Failed to write");
+
+        let mut stream = CubWriter::new("Streaming Test")
+            .streaming(Cursor::new(Vec::new()), Cursor::new(Vec::new()))
+            .expect("Failed to start stream");
+        for airspace in airspaces {
+            stream
+                .add_airspace(airspace)
+                .expect("Failed to add airspace");
+        }
+        let streamed = stream.finish().expect("Failed to finish stream");
+
+        assert_eq!(streamed.into_inner(), buffered.into_inner());
+    }
+
+    #[test]
+    fn streaming_patches_header_at_writer_position() {
+        let airspace = Airspace {
+            name: "Offset Test".to_string(),
+            points: vec![
+                Point::lat_lon(0.5, 0.5),
+                Point::lat_lon(0.51, 0.51),
+                Point::lat_lon(0.5, 0.52),
+            ],
+            ..Default::default()
+        };
+
+        // Start with unrelated data in both the output and the spill buffer
+        let mut output = Cursor::new(b"prefix".to_vec());
+        output.set_position(6);
+        let mut spill = Cursor::new(b"junk".to_vec());
+        spill.set_position(4);
+
+        let mut stream = CubWriter::new("Offset Test")
+            .streaming(output, spill)
+            .expect("Failed to start stream");
+        stream
+            .add_airspace(airspace)
+            .expect("Failed to add airspace");
+        let output = stream.finish().expect("Failed to finish stream");
+
+        let bytes = output.into_inner();
+        assert_eq!(&bytes[..6], b"prefix");
+
+        let header = Header::read(&mut &bytes[6..]).expect("Failed to read header");
+        assert_eq!(header.hdr_items, 1);
+        assert_eq!(header.max_pts, 3);
+
+        let mut reader = CubReader::new(Cursor::new(&bytes[6..])).expect("Failed to read");
+
+        let airspaces: Vec<_> = reader
+            .read_airspaces()
+            .collect::<Result<_>>()
+            .expect("Failed to read airspaces");
+        assert_eq!(airspaces.len(), 1);
+        assert_eq!(airspaces[0].name, "Offset Test");
+        assert_eq!(airspaces[0].points.len(), 3);
+    }
 }